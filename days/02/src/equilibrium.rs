@@ -0,0 +1,246 @@
+use crate::linear_program::{LinearProgram, LinearProgramError};
+use crate::read_and_parse_and_score::{read_lines, ParseAndScoreRoundError};
+use crate::round::{Round, RoundAction};
+use crate::round_parser::{RoundActionParser, RoundParser};
+use crate::score_round::score_round;
+
+const ACTIONS: [RoundAction; 3] = [RoundAction::Rock, RoundAction::Paper, RoundAction::Scissors];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MixedStrategy {
+    pub rock: f64,
+    pub paper: f64,
+    pub scissors: f64,
+}
+
+impl MixedStrategy {
+    pub fn new(rock: f64, paper: f64, scissors: f64) -> Self {
+        MixedStrategy {
+            rock,
+            paper,
+            scissors,
+        }
+    }
+
+    pub fn pure(action: RoundAction) -> Self {
+        let mut strategy = Self::new(0.0, 0.0, 0.0);
+        strategy.set_probability(action, 1.0);
+
+        strategy
+    }
+
+    pub fn probability(&self, action: RoundAction) -> f64 {
+        match action {
+            RoundAction::Rock => self.rock,
+            RoundAction::Paper => self.paper,
+            RoundAction::Scissors => self.scissors,
+        }
+    }
+
+    fn set_probability(&mut self, action: RoundAction, probability: f64) {
+        match action {
+            RoundAction::Rock => self.rock = probability,
+            RoundAction::Paper => self.paper = probability,
+            RoundAction::Scissors => self.scissors = probability,
+        }
+    }
+
+    fn from_weights(weights: &[f64]) -> Self {
+        let total: f64 = weights.iter().sum();
+
+        Self::new(weights[0] / total, weights[1] / total, weights[2] / total)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Equilibrium {
+    // The user strategy that maximizes the worst-case expected score.
+    pub strategy: MixedStrategy,
+    // The opponent strategy that holds the user to that score.
+    pub opponent_strategy: MixedStrategy,
+    pub value: f64,
+}
+
+// The expected score_round for the user, with both sides playing
+// independently.
+pub fn expected_score(strategy: &MixedStrategy, opponent_strategy: &MixedStrategy) -> f64 {
+    ACTIONS
+        .iter()
+        .flat_map(|&user_action| {
+            ACTIONS.iter().map(move |&opponent_action| {
+                strategy.probability(user_action)
+                    * opponent_strategy.probability(opponent_action)
+                    * score_round(&Round::new(user_action, opponent_action)) as f64
+            })
+        })
+        .sum()
+}
+
+// The user action that scores best against the opponent strategy, and its
+// expected score.
+pub fn best_response(opponent_strategy: &MixedStrategy) -> (RoundAction, f64) {
+    ACTIONS
+        .iter()
+        .map(|&action| {
+            (
+                action,
+                expected_score(&MixedStrategy::pure(action), opponent_strategy),
+            )
+        })
+        .fold((RoundAction::Rock, f64::MIN), |best, candidate| {
+            if candidate.1 > best.1 {
+                candidate
+            } else {
+                best
+            }
+        })
+}
+
+// The user's expected score when the opponent, knowing the user's strategy,
+// plays whatever maximizes their own score. score_round isn't zero-sum, so
+// this can differ from the maximin value. Ties between opponent actions are
+// broken against the user.
+pub fn expected_score_against_best_response(strategy: &MixedStrategy) -> f64 {
+    let mut best: Option<(f64, f64)> = None;
+
+    for &opponent_action in &ACTIONS {
+        let opponent = MixedStrategy::pure(opponent_action);
+        let opponent_score = expected_score(&opponent, strategy);
+        let user_score = expected_score(strategy, &opponent);
+
+        best = match best {
+            Some((best_opponent_score, best_user_score))
+                if best_opponent_score > opponent_score
+                    || (best_opponent_score == opponent_score && best_user_score <= user_score) =>
+            {
+                Some((best_opponent_score, best_user_score))
+            }
+            _ => Some((opponent_score, user_score)),
+        };
+    }
+
+    best.map(|(_, user_score)| user_score).unwrap_or(0.0)
+}
+
+// Solves for the user's maximin strategy. Every score is positive, so the
+// game value is too, and the opponent's side reduces to the standard-form
+// program: maximize sum(z) subject to payoff . z <= 1. The game value is the
+// reciprocal of the optimum, the opponent strategy is z scaled by it, and the
+// user strategy comes from the constraint duals.
+pub fn solve_equilibrium() -> Result<Equilibrium, LinearProgramError> {
+    let mut program = LinearProgram::new(vec![1.0; ACTIONS.len()]);
+
+    for &user_action in &ACTIONS {
+        let payoffs = ACTIONS
+            .iter()
+            .map(|&opponent_action| score_round(&Round::new(user_action, opponent_action)) as f64)
+            .collect();
+
+        program.add_constraint(payoffs, 1.0);
+    }
+
+    let solution = program.solve()?;
+
+    Ok(Equilibrium {
+        strategy: MixedStrategy::from_weights(&solution.duals),
+        opponent_strategy: MixedStrategy::from_weights(&solution.variables),
+        value: 1.0 / solution.value,
+    })
+}
+
+// The opponent's action frequencies over a guide file.
+pub fn infer_opponent_strategy<T>(
+    filename: &str,
+    parser: RoundParser<T>,
+) -> Result<MixedStrategy, ParseAndScoreRoundError>
+where
+    T: RoundActionParser,
+{
+    let mut counts = [0.0; 3];
+
+    for line in read_lines(filename)? {
+        let round = parser.parse(line?)?;
+        let index = ACTIONS
+            .iter()
+            .position(|&action| action == round.opponent_action)
+            .unwrap();
+
+        counts[index] += 1.0;
+    }
+
+    if counts.iter().sum::<f64>() == 0.0 {
+        return Ok(MixedStrategy::new(1.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0));
+    }
+
+    Ok(MixedStrategy::from_weights(&counts))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-6,
+            "expected {} to be close to {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_expected_score() {
+        let uniform = MixedStrategy::new(1.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0);
+
+        assert_close(expected_score(&uniform, &uniform), 5.0);
+        assert_close(
+            expected_score(
+                &MixedStrategy::pure(RoundAction::Rock),
+                &MixedStrategy::pure(RoundAction::Scissors),
+            ),
+            7.0,
+        );
+    }
+
+    #[test]
+    fn test_best_response() {
+        let uniform = MixedStrategy::new(1.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0);
+
+        let (action, score) = best_response(&uniform);
+
+        assert_eq!(action, RoundAction::Scissors);
+        assert_close(score, 6.0);
+    }
+
+    #[test]
+    fn test_solve_equilibrium() {
+        let equilibrium = solve_equilibrium().unwrap();
+        let strategy = equilibrium.strategy;
+
+        assert_close(strategy.rock + strategy.paper + strategy.scissors, 1.0);
+
+        // The maximin strategy guarantees the game value against every
+        // opponent action, and the opponent strategy holds every user action
+        // to at most that value.
+        for action in ACTIONS {
+            let pure = MixedStrategy::pure(action);
+
+            assert!(expected_score(&strategy, &pure) >= equilibrium.value - 1e-6);
+            assert!(
+                expected_score(&pure, &equilibrium.opponent_strategy) <= equilibrium.value + 1e-6
+            );
+        }
+
+        assert!(expected_score_against_best_response(&strategy) >= equilibrium.value - 1e-6);
+    }
+
+    #[test]
+    fn test_infer_opponent_strategy() {
+        let strategy =
+            infer_opponent_strategy("fixtures/rounds.txt", RoundParser::default()).unwrap();
+
+        assert_close(strategy.rock, 1.0 / 3.0);
+        assert_close(strategy.paper, 1.0 / 3.0);
+        assert_close(strategy.scissors, 1.0 / 3.0);
+    }
+}
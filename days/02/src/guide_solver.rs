@@ -0,0 +1,210 @@
+use crate::round::{Round, RoundAction};
+use crate::score_round::score_round;
+use thiserror::Error;
+
+const USER_ACTIONS: [RoundAction; 3] =
+    [RoundAction::Rock, RoundAction::Paper, RoundAction::Scissors];
+
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum SolveGuideError {
+    #[error("Score {target} is not reachable, reachable scores range from {min} to {max}")]
+    UnreachableScoreError { target: i32, min: i32, max: i32 },
+}
+
+// Returns the lowest and highest total score reachable against the given
+// opponent actions. Not every score between the two is necessarily reachable,
+// since each round only has three possible scores.
+pub fn reachable_score_range(opponent_actions: &[RoundAction]) -> (i32, i32) {
+    opponent_actions
+        .iter()
+        .map(|&opponent_action| {
+            let scores = round_scores(opponent_action);
+            (*scores.iter().min().unwrap(), *scores.iter().max().unwrap())
+        })
+        .fold((0, 0), |(min, max), (round_min, round_max)| {
+            (min + round_min, max + round_max)
+        })
+}
+
+// Finds user actions for every opponent action so that the rounds score
+// exactly `target` in total. The resulting rounds can be written out under
+// either interpretation with a RoundEncoder.
+pub fn solve_guide(
+    opponent_actions: &[RoundAction],
+    target: i32,
+) -> Result<Vec<Round>, SolveGuideError> {
+    let (min, max) = reachable_score_range(opponent_actions);
+    let unreachable = SolveGuideError::UnreachableScoreError { target, min, max };
+
+    if target < min || target > max {
+        return Err(unreachable);
+    }
+
+    if !reachable_scores(opponent_actions).contains(target as usize) {
+        return Err(unreachable);
+    }
+
+    let mut rounds = Vec::with_capacity(opponent_actions.len());
+    assign_rounds(opponent_actions, target as usize, &mut rounds);
+
+    Ok(rounds)
+}
+
+// Appends rounds against the opponent actions that score exactly `target`,
+// which must be reachable.
+//
+// Keeping every round's reachable set would take memory quadratic in the
+// guide's length, so instead this splits the guide in half, finds how much of
+// the target each half can score, and recurses. Only two sets are alive at
+// once, at the cost of a log factor in time.
+fn assign_rounds(opponent_actions: &[RoundAction], target: usize, rounds: &mut Vec<Round>) {
+    if let [opponent_action] = opponent_actions {
+        let round = USER_ACTIONS
+            .iter()
+            .map(|&user_action| Round::new(user_action, *opponent_action))
+            .find(|round| score_round(round) as usize == target)
+            .unwrap();
+
+        rounds.push(round);
+        return;
+    }
+
+    if opponent_actions.is_empty() {
+        return;
+    }
+
+    let (left, right) = opponent_actions.split_at(opponent_actions.len() / 2);
+    let left_target = {
+        let left_scores = reachable_scores(left);
+        let right_scores = reachable_scores(right);
+
+        (0..=target)
+            .find(|&score| left_scores.contains(score) && right_scores.contains(target - score))
+            .unwrap()
+    };
+
+    assign_rounds(left, left_target, rounds);
+    assign_rounds(right, target - left_target, rounds);
+}
+
+// A bitset of the total scores reachable over the given rounds.
+struct ScoreSet {
+    words: Vec<u64>,
+}
+
+impl ScoreSet {
+    fn contains(&self, score: usize) -> bool {
+        self.words
+            .get(score / 64)
+            .is_some_and(|word| word & (1 << (score % 64)) != 0)
+    }
+}
+
+fn reachable_scores(opponent_actions: &[RoundAction]) -> ScoreSet {
+    let (_, max) = reachable_score_range(opponent_actions);
+    let len = max as usize / 64 + 1;
+
+    let mut words = vec![0u64; len];
+    words[0] = 1;
+
+    for &opponent_action in opponent_actions {
+        let mut next = vec![0u64; len];
+
+        for round_score in round_scores(opponent_action) {
+            shift_or(&mut next, &words, round_score as usize);
+        }
+
+        words = next;
+    }
+
+    ScoreSet { words }
+}
+
+// ORs `source` shifted up by `shift` bits into `target`, dropping any bits
+// shifted past its end.
+fn shift_or(target: &mut [u64], source: &[u64], shift: usize) {
+    let (word_shift, bit_shift) = (shift / 64, shift % 64);
+
+    for i in (word_shift..target.len()).rev() {
+        let low = source[i - word_shift];
+        let mut shifted = low << bit_shift;
+
+        if bit_shift > 0 && i > word_shift {
+            shifted |= source[i - word_shift - 1] >> (64 - bit_shift);
+        }
+
+        target[i] |= shifted;
+    }
+}
+
+fn round_scores(opponent_action: RoundAction) -> [i32; 3] {
+    USER_ACTIONS.map(|user_action| score_round(&Round::new(user_action, opponent_action)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OPPONENT_ACTIONS: [RoundAction; 3] =
+        [RoundAction::Rock, RoundAction::Paper, RoundAction::Scissors];
+
+    #[test]
+    fn test_reachable_score_range() {
+        assert_eq!(reachable_score_range(&OPPONENT_ACTIONS), (6, 24));
+        assert_eq!(reachable_score_range(&[]), (0, 0));
+    }
+
+    #[test]
+    fn test_solve_guide() {
+        for target in [6, 15, 24] {
+            let rounds = solve_guide(&OPPONENT_ACTIONS, target).unwrap();
+            let opponent_actions: Vec<RoundAction> =
+                rounds.iter().map(|round| round.opponent_action).collect();
+
+            assert_eq!(opponent_actions, OPPONENT_ACTIONS);
+            assert_eq!(rounds.iter().map(score_round).sum::<i32>(), target);
+        }
+    }
+
+    #[test]
+    fn test_solve_guide_long_guide() {
+        let opponent_actions: Vec<RoundAction> = OPPONENT_ACTIONS
+            .iter()
+            .copied()
+            .cycle()
+            .take(2500)
+            .collect();
+
+        for target in [15000, 15001, 2500 * 3 + 5] {
+            let rounds = solve_guide(&opponent_actions, target).unwrap();
+
+            assert_eq!(rounds.len(), 2500);
+            assert_eq!(rounds.iter().map(score_round).sum::<i32>(), target);
+        }
+    }
+
+    #[test]
+    fn test_solve_guide_out_of_range() {
+        assert_eq!(
+            solve_guide(&OPPONENT_ACTIONS, 25).unwrap_err(),
+            SolveGuideError::UnreachableScoreError {
+                target: 25,
+                min: 6,
+                max: 24
+            }
+        );
+    }
+
+    #[test]
+    fn test_solve_guide_gap_in_range() {
+        // Against rock the only possible scores are 3, 4 and 8.
+        assert_eq!(
+            solve_guide(&[RoundAction::Rock], 5).unwrap_err(),
+            SolveGuideError::UnreachableScoreError {
+                target: 5,
+                min: 3,
+                max: 8
+            }
+        );
+    }
+}
@@ -1,4 +1,13 @@
+pub mod equilibrium;
+pub mod guide_solver;
+pub mod linear_program;
+pub mod parallel_score;
+pub mod player;
 pub mod read_and_parse_and_score;
 pub mod round;
+pub mod round_encoder;
 pub mod round_parser;
+pub mod round_reader;
 pub mod score_round;
+pub mod simulation;
+pub mod tournament;
@@ -0,0 +1,189 @@
+use thiserror::Error;
+
+const EPSILON: f64 = 1e-9;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum LinearProgramError {
+    #[error("Constraint {0} has a negative bound")]
+    NegativeBoundError(usize),
+    #[error("Constraint {0} has the wrong number of coefficients")]
+    DimensionError(usize),
+    #[error("Linear program is unbounded")]
+    UnboundedError(),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct LinearProgramSolution {
+    pub value: f64,
+    pub variables: Vec<f64>,
+    // The shadow price of each constraint, in the order they were added.
+    pub duals: Vec<f64>,
+}
+
+// A linear program in standard form: maximize `objective . x` subject to
+// `coefficients . x <= bound` for each constraint, with x >= 0. Bounds must be
+// non-negative so that the all-slack basis is a feasible starting point, which
+// is all the game solvers in this crate need.
+pub struct LinearProgram {
+    objective: Vec<f64>,
+    constraints: Vec<(Vec<f64>, f64)>,
+}
+
+impl LinearProgram {
+    pub fn new(objective: Vec<f64>) -> Self {
+        LinearProgram {
+            objective,
+            constraints: Vec::new(),
+        }
+    }
+
+    pub fn add_constraint(&mut self, coefficients: Vec<f64>, bound: f64) {
+        self.constraints.push((coefficients, bound));
+    }
+
+    // Solves with the tableau simplex method, using Bland's rule to avoid
+    // cycling on degenerate pivots.
+    pub fn solve(&self) -> Result<LinearProgramSolution, LinearProgramError> {
+        let variable_count = self.objective.len();
+        let constraint_count = self.constraints.len();
+        let width = variable_count + constraint_count + 1;
+
+        let mut tableau = Vec::with_capacity(constraint_count + 1);
+
+        for (i, (coefficients, bound)) in self.constraints.iter().enumerate() {
+            if coefficients.len() != variable_count {
+                return Err(LinearProgramError::DimensionError(i));
+            }
+
+            if *bound < 0.0 {
+                return Err(LinearProgramError::NegativeBoundError(i));
+            }
+
+            let mut row = vec![0.0; width];
+            row[..variable_count].copy_from_slice(coefficients);
+            row[variable_count + i] = 1.0;
+            row[width - 1] = *bound;
+            tableau.push(row);
+        }
+
+        let mut objective_row = vec![0.0; width];
+        for (cell, coefficient) in objective_row.iter_mut().zip(&self.objective) {
+            *cell = -coefficient;
+        }
+        tableau.push(objective_row);
+
+        let mut basis: Vec<usize> = (variable_count..variable_count + constraint_count).collect();
+
+        while let Some(entering) =
+            (0..width - 1).find(|&column| tableau[constraint_count][column] < -EPSILON)
+        {
+            let leaving = (0..constraint_count)
+                .filter(|&row| tableau[row][entering] > EPSILON)
+                .min_by(|&a, &b| {
+                    let ratio_a = tableau[a][width - 1] / tableau[a][entering];
+                    let ratio_b = tableau[b][width - 1] / tableau[b][entering];
+
+                    ratio_a
+                        .partial_cmp(&ratio_b)
+                        .unwrap()
+                        .then(basis[a].cmp(&basis[b]))
+                })
+                .ok_or(LinearProgramError::UnboundedError())?;
+
+            pivot(&mut tableau, leaving, entering);
+            basis[leaving] = entering;
+        }
+
+        let mut variables = vec![0.0; variable_count];
+        for (row, &column) in basis.iter().enumerate() {
+            if column < variable_count {
+                variables[column] = tableau[row][width - 1];
+            }
+        }
+
+        let objective_row = &tableau[constraint_count];
+
+        Ok(LinearProgramSolution {
+            value: objective_row[width - 1],
+            variables,
+            duals: objective_row[variable_count..width - 1].to_vec(),
+        })
+    }
+}
+
+fn pivot(tableau: &mut [Vec<f64>], pivot_row: usize, pivot_column: usize) {
+    let pivot_value = tableau[pivot_row][pivot_column];
+    for cell in tableau[pivot_row].iter_mut() {
+        *cell /= pivot_value;
+    }
+
+    let pivot_row_values = tableau[pivot_row].clone();
+
+    for (i, row) in tableau.iter_mut().enumerate() {
+        if i == pivot_row {
+            continue;
+        }
+
+        let factor = row[pivot_column];
+        if factor.abs() < EPSILON {
+            continue;
+        }
+
+        for (cell, pivot_cell) in row.iter_mut().zip(&pivot_row_values) {
+            *cell -= factor * pivot_cell;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-6,
+            "expected {} to be close to {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_solve() {
+        let mut program = LinearProgram::new(vec![3.0, 5.0]);
+        program.add_constraint(vec![1.0, 0.0], 4.0);
+        program.add_constraint(vec![0.0, 2.0], 12.0);
+        program.add_constraint(vec![3.0, 2.0], 18.0);
+
+        let solution = program.solve().unwrap();
+
+        assert_close(solution.value, 36.0);
+        assert_close(solution.variables[0], 2.0);
+        assert_close(solution.variables[1], 6.0);
+        assert_close(solution.duals[0], 0.0);
+        assert_close(solution.duals[1], 1.5);
+        assert_close(solution.duals[2], 1.0);
+    }
+
+    #[test]
+    fn test_solve_unbounded() {
+        let mut program = LinearProgram::new(vec![1.0, 1.0]);
+        program.add_constraint(vec![1.0, -1.0], 1.0);
+
+        assert_eq!(
+            program.solve().unwrap_err(),
+            LinearProgramError::UnboundedError()
+        );
+    }
+
+    #[test]
+    fn test_solve_negative_bound() {
+        let mut program = LinearProgram::new(vec![1.0]);
+        program.add_constraint(vec![1.0], -1.0);
+
+        assert_eq!(
+            program.solve().unwrap_err(),
+            LinearProgramError::NegativeBoundError(0)
+        );
+    }
+}
@@ -0,0 +1,291 @@
+use crate::round_parser::{ParseRoundError, RoundActionParser, RoundParser};
+use crate::score_round::score_round;
+use rayon::prelude::*;
+use std::fs::File;
+use std::io::{self, Read};
+use thiserror::Error;
+
+const BLOCK_SIZE: usize = 64 * 1024 * 1024;
+const CHUNK_SIZE: usize = 1024 * 1024;
+
+#[derive(Debug, Error)]
+pub enum ParallelScoreError {
+    #[error("Error reading file")]
+    IoError(#[from] std::io::Error),
+    #[error("Error parsing round on line {0}: {1}")]
+    ParseRoundError(usize, ParseRoundError),
+}
+
+// Scores the same lines as read_and_parse_and_score, with the same result,
+// but reads the file in large blocks and scores each block's lines in
+// parallel. The total is an i64 since guides this large can overflow an i32.
+pub fn read_and_parse_and_score_parallel<T>(
+    filename: &str,
+    parser: RoundParser<T>,
+) -> Result<i64, ParallelScoreError>
+where
+    T: RoundActionParser + Sync,
+{
+    let file = File::open(filename)?;
+
+    score_reader(file, &parser, BLOCK_SIZE, CHUNK_SIZE)
+}
+
+pub fn score_bytes<T>(bytes: &[u8], parser: &RoundParser<T>) -> Result<i64, ParallelScoreError>
+where
+    T: RoundActionParser + Sync,
+{
+    score_block(bytes, parser, CHUNK_SIZE)
+        .map(|(score, _)| score)
+        .map_err(|(line_number, error)| error.located(line_number))
+}
+
+// Why a line couldn't be scored, before its line number within the whole
+// file is known.
+#[derive(Debug)]
+enum LineError {
+    // BufRead::lines fails on these, so the sequential path sees an IoError.
+    InvalidUtf8,
+    Parse(ParseRoundError),
+}
+
+impl LineError {
+    fn located(self, line_number: usize) -> ParallelScoreError {
+        match self {
+            LineError::InvalidUtf8 => ParallelScoreError::IoError(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "stream did not contain valid UTF-8",
+            )),
+            LineError::Parse(error) => ParallelScoreError::ParseRoundError(line_number, error),
+        }
+    }
+}
+
+fn score_reader<R, T>(
+    mut reader: R,
+    parser: &RoundParser<T>,
+    block_size: usize,
+    chunk_size: usize,
+) -> Result<i64, ParallelScoreError>
+where
+    R: Read,
+    T: RoundActionParser + Sync,
+{
+    let mut buffer = Vec::with_capacity(block_size);
+    let mut total_score = 0;
+    let mut lines_before = 0;
+
+    loop {
+        // A line longer than a block is carried over whole, so keep reading
+        // full blocks until it ends.
+        let wanted = match block_size.saturating_sub(buffer.len()) {
+            0 => block_size.max(1),
+            wanted => wanted,
+        };
+        let read = (&mut reader).take(wanted as u64).read_to_end(&mut buffer)?;
+        let at_end = read < wanted;
+
+        // Only score up to the last complete line, and carry the rest over
+        // into the next block.
+        let end = match buffer.iter().rposition(|&b| b == b'\n') {
+            _ if at_end => buffer.len(),
+            Some(position) => position + 1,
+            None => continue,
+        };
+
+        let (score, lines) = score_block(&buffer[..end], parser, chunk_size)
+            .map_err(|(line_number, error)| error.located(lines_before + line_number))?;
+
+        total_score += score;
+        lines_before += lines;
+        buffer.drain(..end);
+
+        if at_end {
+            return Ok(total_score);
+        }
+    }
+}
+
+// Splits the block into chunks on line boundaries and scores them in
+// parallel. Returns the score and line count, or the 1-based line number and
+// error of the first invalid line.
+fn score_block<T>(
+    bytes: &[u8],
+    parser: &RoundParser<T>,
+    chunk_size: usize,
+) -> Result<(i64, usize), (usize, LineError)>
+where
+    T: RoundActionParser + Sync,
+{
+    let mut chunks = Vec::new();
+    let mut start = 0;
+
+    while start < bytes.len() {
+        let end = (start + chunk_size).min(bytes.len());
+        let end = match bytes[end..].iter().position(|&b| b == b'\n') {
+            Some(offset) => end + offset + 1,
+            None => bytes.len(),
+        };
+
+        chunks.push(&bytes[start..end]);
+        start = end;
+    }
+
+    let results: Vec<_> = chunks
+        .par_iter()
+        .map(|chunk| score_chunk(chunk, parser))
+        .collect();
+
+    let mut total_score = 0;
+    let mut lines_before = 0;
+
+    for result in results {
+        match result {
+            Ok((score, lines)) => {
+                total_score += score;
+                lines_before += lines;
+            }
+            Err((line_number, error)) => return Err((lines_before + line_number, error)),
+        }
+    }
+
+    Ok((total_score, lines_before))
+}
+
+// Splits lines the way BufRead::lines does: on "\n", dropping a trailing "\r",
+// with no empty line after a final newline.
+fn score_chunk<T>(chunk: &[u8], parser: &RoundParser<T>) -> Result<(i64, usize), (usize, LineError)>
+where
+    T: RoundActionParser,
+{
+    if chunk.is_empty() {
+        return Ok((0, 0));
+    }
+
+    let body = chunk.strip_suffix(b"\n").unwrap_or(chunk);
+    let mut total_score = 0;
+    let mut lines = 0;
+
+    for line in body.split(|&b| b == b'\n') {
+        lines += 1;
+
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        if std::str::from_utf8(line).is_err() {
+            return Err((lines, LineError::InvalidUtf8));
+        }
+
+        let round = parser
+            .parse_bytes(line)
+            .map_err(|error| (lines, LineError::Parse(error)))?;
+
+        total_score += score_round(&round) as i64;
+    }
+
+    Ok((total_score, lines))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_and_parse_and_score::read_and_parse_and_score;
+    use crate::round_parser::ContextualRoundActionParser;
+
+    fn synthetic_guide(lines: usize) -> String {
+        let opponent_codes = ['A', 'B', 'C'];
+        let user_codes = ['X', 'Y', 'Z'];
+
+        (0..lines)
+            .map(|i| format!("{} {}\n", opponent_codes[i % 3], user_codes[(i / 3) % 3]))
+            .collect()
+    }
+
+    fn sequential_score<T: RoundActionParser>(guide: &str, parser: &RoundParser<T>) -> i64 {
+        guide
+            .lines()
+            .map(|line| score_round(&parser.parse(line.to_string()).unwrap()) as i64)
+            .sum()
+    }
+
+    #[test]
+    fn test_parallel_matches_sequential_on_fixture() {
+        let filename = "fixtures/rounds.txt";
+
+        assert_eq!(
+            read_and_parse_and_score_parallel(filename, RoundParser::default()).unwrap(),
+            read_and_parse_and_score(filename, RoundParser::default()).unwrap() as i64
+        );
+        assert_eq!(
+            read_and_parse_and_score_parallel(
+                filename,
+                RoundParser::new(ContextualRoundActionParser::new())
+            )
+            .unwrap(),
+            read_and_parse_and_score(
+                filename,
+                RoundParser::new(ContextualRoundActionParser::new())
+            )
+            .unwrap() as i64
+        );
+    }
+
+    #[test]
+    fn test_parallel_matches_sequential_across_blocks() {
+        let guide = synthetic_guide(10_000);
+        let parser = RoundParser::new(ContextualRoundActionParser::new());
+
+        // Block and chunk sizes that don't line up with line boundaries.
+        let score = score_reader(guide.as_bytes(), &parser, 1021, 37).unwrap();
+
+        assert_eq!(score, sequential_score(&guide, &parser));
+        assert_eq!(score_bytes(guide.as_bytes(), &parser).unwrap(), score);
+    }
+
+    #[test]
+    fn test_parallel_handles_crlf_and_missing_final_newline() {
+        let parser = RoundParser::default();
+
+        assert_eq!(score_bytes(b"A Y\r\nB X\r\nC Z", &parser).unwrap(), 15);
+    }
+
+    #[test]
+    fn test_parallel_handles_lines_longer_than_a_block() {
+        // Cut at four bytes, the second line would start with a valid "A Y\r"
+        // and the error would land on a line that doesn't exist.
+        let guide = b"A Y\nA Y\r\r\r\nB X\n";
+
+        let error = score_reader(&guide[..], &RoundParser::default(), 4, 37).unwrap_err();
+
+        assert!(matches!(
+            error,
+            ParallelScoreError::ParseRoundError(2, ParseRoundError::InvalidFormatError())
+        ));
+        assert_eq!(
+            score_reader(&b"A Y\nB X\n"[..], &RoundParser::default(), 2, 1).unwrap(),
+            9
+        );
+    }
+
+    #[test]
+    fn test_parallel_rejects_invalid_utf8_like_sequential() {
+        let error = score_bytes(b"A Y\n\xff X\n", &RoundParser::default()).unwrap_err();
+
+        assert!(matches!(
+            error,
+            ParallelScoreError::IoError(error) if error.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+
+    #[test]
+    fn test_parallel_reports_line_number() {
+        let mut guide = synthetic_guide(5_000);
+        guide.push_str("A Q\n");
+        guide.push_str(&synthetic_guide(5_000));
+
+        let error = score_reader(guide.as_bytes(), &RoundParser::default(), 1021, 37).unwrap_err();
+
+        assert!(matches!(
+            error,
+            ParallelScoreError::ParseRoundError(5_001, ParseRoundError::InvalidFormatError())
+        ));
+    }
+}
@@ -0,0 +1,292 @@
+use crate::round::{get_user_action_by_outcome, Round, RoundAction, RoundOutcome};
+use rand::rngs::StdRng;
+use rand::Rng;
+
+const ACTIONS: [RoundAction; 3] = [RoundAction::Rock, RoundAction::Paper, RoundAction::Scissors];
+
+pub trait Player {
+    fn name(&self) -> String;
+    fn choose_action(&mut self, rng: &mut StdRng) -> RoundAction;
+    fn observe(&mut self, own_action: RoundAction, opponent_action: RoundAction);
+}
+
+pub struct ConstantPlayer {
+    action: RoundAction,
+}
+
+impl ConstantPlayer {
+    pub fn new(action: RoundAction) -> Self {
+        ConstantPlayer { action }
+    }
+}
+
+impl Player for ConstantPlayer {
+    fn name(&self) -> String {
+        format!("always-{:?}", self.action).to_lowercase()
+    }
+
+    fn choose_action(&mut self, _rng: &mut StdRng) -> RoundAction {
+        self.action
+    }
+
+    fn observe(&mut self, _own_action: RoundAction, _opponent_action: RoundAction) {}
+}
+
+pub struct RandomPlayer {}
+
+impl RandomPlayer {
+    pub fn new() -> Self {
+        RandomPlayer {}
+    }
+}
+
+impl Default for RandomPlayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Player for RandomPlayer {
+    fn name(&self) -> String {
+        "random".to_string()
+    }
+
+    fn choose_action(&mut self, rng: &mut StdRng) -> RoundAction {
+        ACTIONS[rng.gen_range(0..ACTIONS.len())]
+    }
+
+    fn observe(&mut self, _own_action: RoundAction, _opponent_action: RoundAction) {}
+}
+
+// Plays whatever beats the opponent's most frequent action so far.
+pub struct FrequencyCounterPlayer {
+    counts: [usize; 3],
+}
+
+impl FrequencyCounterPlayer {
+    pub fn new() -> Self {
+        FrequencyCounterPlayer { counts: [0; 3] }
+    }
+}
+
+impl Default for FrequencyCounterPlayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Player for FrequencyCounterPlayer {
+    fn name(&self) -> String {
+        "frequency-counter".to_string()
+    }
+
+    fn choose_action(&mut self, rng: &mut StdRng) -> RoundAction {
+        match predict(&self.counts) {
+            Some(predicted) => get_user_action_by_outcome(predicted, RoundOutcome::Win),
+            None => RandomPlayer::new().choose_action(rng),
+        }
+    }
+
+    fn observe(&mut self, _own_action: RoundAction, opponent_action: RoundAction) {
+        self.counts[action_index(opponent_action)] += 1;
+    }
+}
+
+// Predicts the opponent's next action from the actions that have followed
+// their previous one, and plays whatever beats it.
+pub struct MarkovChainPlayer {
+    transitions: [[usize; 3]; 3],
+    last_opponent_action: Option<RoundAction>,
+}
+
+impl MarkovChainPlayer {
+    pub fn new() -> Self {
+        MarkovChainPlayer {
+            transitions: [[0; 3]; 3],
+            last_opponent_action: None,
+        }
+    }
+}
+
+impl Default for MarkovChainPlayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Player for MarkovChainPlayer {
+    fn name(&self) -> String {
+        "markov-chain".to_string()
+    }
+
+    fn choose_action(&mut self, rng: &mut StdRng) -> RoundAction {
+        let predicted = self
+            .last_opponent_action
+            .and_then(|last| predict(&self.transitions[action_index(last)]));
+
+        match predicted {
+            Some(predicted) => get_user_action_by_outcome(predicted, RoundOutcome::Win),
+            None => RandomPlayer::new().choose_action(rng),
+        }
+    }
+
+    fn observe(&mut self, _own_action: RoundAction, opponent_action: RoundAction) {
+        if let Some(last) = self.last_opponent_action {
+            self.transitions[action_index(last)][action_index(opponent_action)] += 1;
+        }
+
+        self.last_opponent_action = Some(opponent_action);
+    }
+}
+
+// Plays whatever the opponent played last round.
+pub struct CopyLastPlayer {
+    last_opponent_action: Option<RoundAction>,
+}
+
+impl CopyLastPlayer {
+    pub fn new() -> Self {
+        CopyLastPlayer {
+            last_opponent_action: None,
+        }
+    }
+}
+
+impl Default for CopyLastPlayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Player for CopyLastPlayer {
+    fn name(&self) -> String {
+        "copy-last".to_string()
+    }
+
+    fn choose_action(&mut self, rng: &mut StdRng) -> RoundAction {
+        match self.last_opponent_action {
+            Some(action) => action,
+            None => RandomPlayer::new().choose_action(rng),
+        }
+    }
+
+    fn observe(&mut self, _own_action: RoundAction, opponent_action: RoundAction) {
+        self.last_opponent_action = Some(opponent_action);
+    }
+}
+
+// Replays a fixed sequence of actions, wrapping around at the end. Built from
+// a guide, this plays the guide's opponent.
+pub struct ScriptedPlayer {
+    actions: Vec<RoundAction>,
+    position: usize,
+}
+
+impl ScriptedPlayer {
+    pub fn new(actions: Vec<RoundAction>) -> Self {
+        ScriptedPlayer {
+            actions,
+            position: 0,
+        }
+    }
+
+    pub fn from_opponent_actions(rounds: &[Round]) -> Self {
+        Self::new(rounds.iter().map(|round| round.opponent_action).collect())
+    }
+}
+
+impl Player for ScriptedPlayer {
+    fn name(&self) -> String {
+        "scripted".to_string()
+    }
+
+    fn choose_action(&mut self, rng: &mut StdRng) -> RoundAction {
+        if self.actions.is_empty() {
+            return RandomPlayer::new().choose_action(rng);
+        }
+
+        let action = self.actions[self.position % self.actions.len()];
+        self.position += 1;
+
+        action
+    }
+
+    fn observe(&mut self, _own_action: RoundAction, _opponent_action: RoundAction) {}
+}
+
+fn action_index(action: RoundAction) -> usize {
+    match action {
+        RoundAction::Rock => 0,
+        RoundAction::Paper => 1,
+        RoundAction::Scissors => 2,
+    }
+}
+
+// Ties go to the earlier action so predictions are deterministic.
+fn predict(counts: &[usize; 3]) -> Option<RoundAction> {
+    let (index, &count) = counts
+        .iter()
+        .enumerate()
+        .rev()
+        .max_by_key(|(_, &count)| count)?;
+
+    if count == 0 {
+        return None;
+    }
+
+    Some(ACTIONS[index])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_frequency_counter_beats_most_frequent() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut player = FrequencyCounterPlayer::new();
+
+        player.observe(RoundAction::Rock, RoundAction::Paper);
+        player.observe(RoundAction::Rock, RoundAction::Paper);
+        player.observe(RoundAction::Rock, RoundAction::Rock);
+
+        assert_eq!(player.choose_action(&mut rng), RoundAction::Scissors);
+    }
+
+    #[test]
+    fn test_markov_chain_predicts_transition() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut player = MarkovChainPlayer::new();
+
+        for action in [RoundAction::Rock, RoundAction::Paper, RoundAction::Rock] {
+            player.observe(RoundAction::Rock, action);
+        }
+
+        // Rock has always been followed by paper.
+        assert_eq!(player.choose_action(&mut rng), RoundAction::Scissors);
+    }
+
+    #[test]
+    fn test_copy_last() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut player = CopyLastPlayer::new();
+
+        player.observe(RoundAction::Rock, RoundAction::Scissors);
+
+        assert_eq!(player.choose_action(&mut rng), RoundAction::Scissors);
+    }
+
+    #[test]
+    fn test_scripted_wraps_around() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut player = ScriptedPlayer::new(vec![RoundAction::Rock, RoundAction::Paper]);
+
+        let actions: Vec<RoundAction> = (0..3).map(|_| player.choose_action(&mut rng)).collect();
+
+        assert_eq!(
+            actions,
+            vec![RoundAction::Rock, RoundAction::Paper, RoundAction::Rock]
+        );
+    }
+}
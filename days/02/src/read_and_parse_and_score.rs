@@ -1,4 +1,5 @@
-use crate::round_parser::{ParseRoundError, RoundActionParser, RoundParser};
+use crate::round_parser::{split_line, ParseRoundError, RoundActionParser, RoundParser};
+use crate::round_reader::{LocatedError, RoundReader};
 use crate::score_round::score_round;
 use std::fs::File;
 use std::io::{self, BufRead};
@@ -33,6 +34,52 @@ where
     Ok(total_score)
 }
 
+// Reads the guide once and scores it under every interpretation at the same
+// time. Scores are returned in the same order as the action parsers.
+pub fn read_and_parse_and_score_many(
+    filename: &str,
+    action_parsers: &[&dyn RoundActionParser],
+) -> Result<Vec<i32>, ParseAndScoreRoundError> {
+    let mut total_scores = vec![0; action_parsers.len()];
+
+    for line in read_lines(filename)? {
+        let line = line?;
+        let (opponent_action_code, user_action_code) = split_line(&line)?;
+
+        for (action_parser, total_score) in action_parsers.iter().zip(total_scores.iter_mut()) {
+            let round = action_parser.parse_codes(opponent_action_code, user_action_code)?;
+            *total_score += score_round(&round);
+        }
+    }
+
+    Ok(total_scores)
+}
+
+// Like read_and_parse_and_score, but scores every valid line and returns the
+// errors for the invalid ones alongside the score.
+pub fn read_and_parse_and_score_skipping_invalid<T>(
+    filename: &str,
+    parser: RoundParser<T>,
+) -> Result<(i32, Vec<LocatedError>), ParseAndScoreRoundError>
+where
+    T: RoundActionParser,
+{
+    let file = std::fs::File::open(filename)?;
+    let reader = std::io::BufReader::new(file);
+
+    let mut total_score = 0;
+    let mut errors = Vec::new();
+
+    for result in RoundReader::new(reader, parser) {
+        match result {
+            Ok(round) => total_score += score_round(&round),
+            Err(error) => errors.push(error),
+        }
+    }
+
+    Ok((total_score, errors))
+}
+
 pub fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
 where
     P: AsRef<Path>,
@@ -44,7 +91,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::round_parser::ContextualRoundActionParser;
+    use crate::round_parser::{ContextualRoundActionParser, DefaultRoundActionParser};
 
     #[test]
     fn test_default_read_and_parse_and_score() {
@@ -65,4 +112,34 @@ mod tests {
 
         assert_eq!(score, 12);
     }
+
+    #[test]
+    fn test_read_and_parse_and_score_many() {
+        let filename = "fixtures/rounds.txt";
+        let default_parser = DefaultRoundActionParser::new();
+        let contextual_parser = ContextualRoundActionParser::new();
+
+        let scores =
+            read_and_parse_and_score_many(filename, &[&default_parser, &contextual_parser])
+                .unwrap();
+
+        assert_eq!(scores, vec![15, 12]);
+    }
+
+    #[test]
+    fn test_read_and_parse_and_score_skipping_invalid() {
+        let filename = "fixtures/rounds_with_errors.txt";
+        let parser = RoundParser::default();
+
+        let (score, errors) = read_and_parse_and_score_skipping_invalid(filename, parser).unwrap();
+
+        assert_eq!(score, 15);
+        assert_eq!(
+            errors
+                .iter()
+                .map(|error| error.line_number)
+                .collect::<Vec<_>>(),
+            vec![3, 5]
+        );
+    }
 }
@@ -51,7 +51,7 @@ pub fn get_opponent_action_by_outcome(
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Round {
     pub opponent_action: RoundAction,
     pub user_action: RoundAction,
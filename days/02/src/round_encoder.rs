@@ -0,0 +1,151 @@
+use crate::round::{Round, RoundAction, RoundOutcome};
+use crate::round_parser::{ContextualRoundActionParser, DefaultRoundActionParser};
+
+// The inverse of RoundParser. Each RoundActionParser knows how to read a
+// guide line, and its RoundActionEncoder counterpart knows how to write one
+// back out under the same interpretation.
+
+pub struct RoundEncoder<T: RoundActionEncoder> {
+    action_encoder: T,
+}
+
+impl<T: RoundActionEncoder> RoundEncoder<T> {
+    pub fn new(action_encoder: T) -> Self {
+        RoundEncoder { action_encoder }
+    }
+
+    pub fn encode(&self, round: &Round) -> String {
+        let opponent_action_code = self
+            .action_encoder
+            .encode_opponent_action(round.opponent_action);
+
+        let user_action_code = self
+            .action_encoder
+            .encode_user_action(round.user_action, round.opponent_action);
+
+        format!("{} {}", opponent_action_code, user_action_code)
+    }
+
+    pub fn encode_all(&self, rounds: &[Round]) -> Vec<String> {
+        rounds.iter().map(|round| self.encode(round)).collect()
+    }
+}
+
+impl Default for RoundEncoder<DefaultRoundActionParser> {
+    fn default() -> Self {
+        Self::new(DefaultRoundActionParser::new())
+    }
+}
+
+pub trait RoundActionEncoder {
+    fn encode_opponent_action(&self, action: RoundAction) -> &'static str;
+    fn encode_user_action(
+        &self,
+        user_action: RoundAction,
+        opponent_action: RoundAction,
+    ) -> &'static str;
+}
+
+impl RoundActionEncoder for DefaultRoundActionParser {
+    fn encode_opponent_action(&self, action: RoundAction) -> &'static str {
+        match action {
+            RoundAction::Rock => "A",
+            RoundAction::Paper => "B",
+            RoundAction::Scissors => "C",
+        }
+    }
+
+    fn encode_user_action(
+        &self,
+        user_action: RoundAction,
+        _opponent_action: RoundAction,
+    ) -> &'static str {
+        match user_action {
+            RoundAction::Rock => "X",
+            RoundAction::Paper => "Y",
+            RoundAction::Scissors => "Z",
+        }
+    }
+}
+
+impl RoundActionEncoder for ContextualRoundActionParser {
+    fn encode_opponent_action(&self, action: RoundAction) -> &'static str {
+        DefaultRoundActionParser::new().encode_opponent_action(action)
+    }
+
+    fn encode_user_action(
+        &self,
+        user_action: RoundAction,
+        opponent_action: RoundAction,
+    ) -> &'static str {
+        match Round::new(user_action, opponent_action).outcome() {
+            RoundOutcome::Loss => "X",
+            RoundOutcome::Draw => "Y",
+            RoundOutcome::Win => "Z",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round_parser::{RoundActionParser, RoundParser};
+
+    const ACTIONS: [RoundAction; 3] =
+        [RoundAction::Rock, RoundAction::Paper, RoundAction::Scissors];
+
+    fn all_rounds() -> Vec<Round> {
+        ACTIONS
+            .iter()
+            .flat_map(|&opponent_action| {
+                ACTIONS
+                    .iter()
+                    .map(move |&user_action| Round::new(user_action, opponent_action))
+            })
+            .collect()
+    }
+
+    fn assert_round_trips<T>(encoder: RoundEncoder<T>, parser: RoundParser<T>)
+    where
+        T: RoundActionEncoder + RoundActionParser,
+    {
+        let rounds = all_rounds();
+
+        let lines = encoder.encode_all(&rounds);
+        let parsed: Vec<Round> = lines
+            .into_iter()
+            .map(|line| parser.parse(line).unwrap())
+            .collect();
+
+        assert_eq!(parsed, rounds);
+    }
+
+    #[test]
+    fn test_default_encode() {
+        let encoder = RoundEncoder::default();
+        let round = Round::new(RoundAction::Paper, RoundAction::Rock);
+
+        assert_eq!(encoder.encode(&round), "A Y");
+    }
+
+    #[test]
+    fn test_contextual_encode() {
+        let encoder = RoundEncoder::new(ContextualRoundActionParser::new());
+        let round = Round::new(RoundAction::Paper, RoundAction::Rock);
+
+        assert_eq!(encoder.encode(&round), "A Z");
+    }
+
+    #[test]
+    fn test_default_round_trip() {
+        assert_round_trips(RoundEncoder::default(), RoundParser::default());
+    }
+
+    #[test]
+    fn test_contextual_round_trip() {
+        assert_round_trips(
+            RoundEncoder::new(ContextualRoundActionParser::new()),
+            RoundParser::new(ContextualRoundActionParser::new()),
+        );
+    }
+}
@@ -30,25 +30,40 @@ impl<T: RoundActionParser> RoundParser<T> {
     }
 
     pub fn parse(&self, line: String) -> Result<Round, ParseRoundError> {
-        let matches = LINE_REGEX
-            .captures(&line)
-            .ok_or(ParseRoundError::InvalidFormatError())?;
+        let (opponent_action_code, user_action_code) = split_line(&line)?;
 
-        let (opponent_action_code, user_action_code) =
-            (matches[1].to_string(), matches[2].to_string());
-
-        let opponent_action = self
-            .action_parser
-            .parse_opponent_action(&opponent_action_code)?;
-
-        let user_action = self
-            .action_parser
-            .parse_user_action(&user_action_code, opponent_action)?;
+        self.action_parser
+            .parse_codes(opponent_action_code, user_action_code)
+    }
 
-        Ok(Round::new(user_action, opponent_action))
+    // A regex-free, allocation-free parse for lines that are already bytes.
+    pub fn parse_bytes(&self, line: &[u8]) -> Result<Round, ParseRoundError> {
+        let (opponent_action_code, user_action_code) = match line {
+            [opponent @ b'A'..=b'C', b' ', user @ b'X'..=b'Z'] => (opponent, user),
+            _ => return Err(ParseRoundError::InvalidFormatError()),
+        };
+
+        // Both codes are single ASCII bytes, so they're always valid UTF-8.
+        self.action_parser.parse_codes(
+            std::str::from_utf8(std::slice::from_ref(opponent_action_code)).unwrap(),
+            std::str::from_utf8(std::slice::from_ref(user_action_code)).unwrap(),
+        )
     }
 }
 
+// Splits a guide line into its opponent and user action codes, independent
+// of how those codes are interpreted.
+pub fn split_line(line: &str) -> Result<(&str, &str), ParseRoundError> {
+    let matches = LINE_REGEX
+        .captures(line)
+        .ok_or(ParseRoundError::InvalidFormatError())?;
+
+    Ok((
+        matches.get(1).unwrap().as_str(),
+        matches.get(2).unwrap().as_str(),
+    ))
+}
+
 impl Default for RoundParser<DefaultRoundActionParser> {
     fn default() -> Self {
         Self::new(DefaultRoundActionParser::new())
@@ -62,7 +77,19 @@ pub trait RoundActionParser {
         s: &str,
         opponent_action: RoundAction,
     ) -> Result<RoundAction, ParseRoundError>;
+
+    fn parse_codes(
+        &self,
+        opponent_action_code: &str,
+        user_action_code: &str,
+    ) -> Result<Round, ParseRoundError> {
+        let opponent_action = self.parse_opponent_action(opponent_action_code)?;
+        let user_action = self.parse_user_action(user_action_code, opponent_action)?;
+
+        Ok(Round::new(user_action, opponent_action))
+    }
 }
+
 pub struct DefaultRoundActionParser {}
 
 impl DefaultRoundActionParser {
@@ -224,4 +251,16 @@ mod test {
         assert_eq!(round.opponent_action, RoundAction::Rock);
         assert_eq!(round.user_action, RoundAction::Rock);
     }
+
+    #[test]
+    fn test_parse_bytes_matches_parse() {
+        let parser = RoundParser::new(ContextualRoundActionParser::new());
+
+        for line in ["A X", "B Y", "C Z", "A", "D X", "A  X", ""] {
+            let from_string = parser.parse(line.to_string());
+            let from_bytes = parser.parse_bytes(line.as_bytes());
+
+            assert_eq!(from_bytes.ok(), from_string.ok());
+        }
+    }
 }
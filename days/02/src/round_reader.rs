@@ -0,0 +1,147 @@
+use crate::read_and_parse_and_score::ParseAndScoreRoundError;
+use crate::round::Round;
+use crate::round_parser::{RoundActionParser, RoundParser};
+use std::io::{self, BufRead};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+#[error("Error on line {line_number}: {error}")]
+pub struct LocatedError {
+    pub line_number: usize,
+    // The offending line, when it could be read at all.
+    pub line: Option<String>,
+    #[source]
+    pub error: ParseAndScoreRoundError,
+}
+
+// Streams rounds from any reader, one line at a time, tagging every error
+// with its 1-based line number. Iteration stops after an IO error, since the
+// reader can't be trusted to make progress past one.
+pub struct RoundReader<R: BufRead, T: RoundActionParser> {
+    lines: io::Lines<R>,
+    parser: RoundParser<T>,
+    line_number: usize,
+    done: bool,
+}
+
+impl<R: BufRead, T: RoundActionParser> RoundReader<R, T> {
+    pub fn new(reader: R, parser: RoundParser<T>) -> Self {
+        RoundReader {
+            lines: reader.lines(),
+            parser,
+            line_number: 0,
+            done: false,
+        }
+    }
+
+    // Reads every line, keeping the valid rounds and collecting the errors
+    // for the rest instead of stopping at the first one.
+    pub fn partition_valid(self) -> (Vec<Round>, Vec<LocatedError>) {
+        let mut rounds = Vec::new();
+        let mut errors = Vec::new();
+
+        for result in self {
+            match result {
+                Ok(round) => rounds.push(round),
+                Err(error) => errors.push(error),
+            }
+        }
+
+        (rounds, errors)
+    }
+}
+
+impl<R: BufRead, T: RoundActionParser> Iterator for RoundReader<R, T> {
+    type Item = Result<Round, LocatedError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let line = self.lines.next()?;
+        self.line_number += 1;
+
+        let line = match line {
+            Ok(line) => line,
+            Err(error) => {
+                self.done = true;
+
+                return Some(Err(LocatedError {
+                    line_number: self.line_number,
+                    line: None,
+                    error: error.into(),
+                }));
+            }
+        };
+
+        Some(
+            self.parser
+                .parse(line.clone())
+                .map_err(|error| LocatedError {
+                    line_number: self.line_number,
+                    line: Some(line),
+                    error: error.into(),
+                }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::round::RoundAction;
+    use crate::round_parser::ParseRoundError;
+
+    #[test]
+    fn test_round_reader() {
+        let input = "A Y\nB X\n".as_bytes();
+
+        let rounds: Vec<Round> = RoundReader::new(input, RoundParser::default())
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(
+            rounds,
+            vec![
+                Round::new(RoundAction::Paper, RoundAction::Rock),
+                Round::new(RoundAction::Rock, RoundAction::Paper),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_round_reader_locates_errors() {
+        let input = "A Y\nB W\nC Z\n".as_bytes();
+
+        let results: Vec<_> = RoundReader::new(input, RoundParser::default()).collect();
+
+        assert_eq!(results.len(), 3);
+        assert!(results[0].is_ok());
+        assert!(results[2].is_ok());
+
+        let error = results[1].as_ref().unwrap_err();
+        assert_eq!(error.line_number, 2);
+        assert_eq!(error.line.as_deref(), Some("B W"));
+        assert!(matches!(
+            error.error,
+            ParseAndScoreRoundError::ParseRoundError(ParseRoundError::InvalidFormatError())
+        ));
+    }
+
+    #[test]
+    fn test_partition_valid() {
+        let input = "A Y\n\nB X\nC\nC Z\n".as_bytes();
+
+        let (rounds, errors) = RoundReader::new(input, RoundParser::default()).partition_valid();
+
+        assert_eq!(rounds.len(), 3);
+        assert_eq!(
+            errors
+                .iter()
+                .map(|error| error.line_number)
+                .collect::<Vec<_>>(),
+            vec![2, 4]
+        );
+    }
+}
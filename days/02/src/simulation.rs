@@ -0,0 +1,136 @@
+use crate::player::Player;
+use crate::round::{Round, RoundOutcome};
+use crate::score_round::score_round;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PlayerStats {
+    pub wins: usize,
+    pub losses: usize,
+    pub draws: usize,
+    pub total_score: i32,
+}
+
+impl PlayerStats {
+    pub fn rounds(&self) -> usize {
+        self.wins + self.losses + self.draws
+    }
+
+    pub fn win_rate(&self) -> f64 {
+        match self.rounds() {
+            0 => 0.0,
+            rounds => self.wins as f64 / rounds as f64,
+        }
+    }
+
+    pub fn average_score(&self) -> f64 {
+        match self.rounds() {
+            0 => 0.0,
+            rounds => self.total_score as f64 / rounds as f64,
+        }
+    }
+
+    fn record(&mut self, round: &Round) {
+        match round.outcome() {
+            RoundOutcome::Win => self.wins += 1,
+            RoundOutcome::Loss => self.losses += 1,
+            RoundOutcome::Draw => self.draws += 1,
+        }
+
+        self.total_score += score_round(round);
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SimulationStats {
+    pub first_player: String,
+    pub second_player: String,
+    pub first: PlayerStats,
+    pub second: PlayerStats,
+}
+
+// Plays `rounds` rounds between two players. Both players draw from the same
+// RNG, so a given seed always produces the same game.
+pub fn simulate(
+    first: &mut dyn Player,
+    second: &mut dyn Player,
+    rounds: usize,
+    seed: u64,
+) -> SimulationStats {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut stats = SimulationStats {
+        first_player: first.name(),
+        second_player: second.name(),
+        first: PlayerStats::default(),
+        second: PlayerStats::default(),
+    };
+
+    for _ in 0..rounds {
+        play_round(first, second, &mut rng, &mut stats.first, &mut stats.second);
+    }
+
+    stats
+}
+
+pub(crate) fn play_round(
+    first: &mut dyn Player,
+    second: &mut dyn Player,
+    rng: &mut StdRng,
+    first_stats: &mut PlayerStats,
+    second_stats: &mut PlayerStats,
+) {
+    let first_action = first.choose_action(rng);
+    let second_action = second.choose_action(rng);
+
+    first_stats.record(&Round::new(first_action, second_action));
+    second_stats.record(&Round::new(second_action, first_action));
+
+    first.observe(first_action, second_action);
+    second.observe(second_action, first_action);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::*;
+    use crate::round::RoundAction;
+
+    #[test]
+    fn test_simulate_constant_players() {
+        let mut rock = ConstantPlayer::new(RoundAction::Rock);
+        let mut paper = ConstantPlayer::new(RoundAction::Paper);
+
+        let stats = simulate(&mut rock, &mut paper, 10, 0);
+
+        assert_eq!(stats.first_player, "always-rock");
+        assert_eq!(stats.first.losses, 10);
+        assert_eq!(stats.first.total_score, 10);
+        assert_eq!(stats.second.wins, 10);
+        assert_eq!(stats.second.total_score, 80);
+        assert_eq!(stats.second.win_rate(), 1.0);
+    }
+
+    #[test]
+    fn test_simulate_is_deterministic_for_seed() {
+        let first = simulate(&mut RandomPlayer::new(), &mut RandomPlayer::new(), 100, 42);
+        let second = simulate(&mut RandomPlayer::new(), &mut RandomPlayer::new(), 100, 42);
+
+        assert_eq!(first, second);
+        assert_eq!(first.first.rounds(), 100);
+    }
+
+    #[test]
+    fn test_markov_chain_exploits_cycle() {
+        let mut cycle = ScriptedPlayer::new(vec![
+            RoundAction::Rock,
+            RoundAction::Paper,
+            RoundAction::Scissors,
+        ]);
+        let mut markov = MarkovChainPlayer::new();
+
+        let stats = simulate(&mut cycle, &mut markov, 300, 0);
+
+        assert!(stats.second.wins >= 295);
+    }
+}
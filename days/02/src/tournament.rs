@@ -0,0 +1,470 @@
+use crate::player::{Player, ScriptedPlayer};
+use crate::read_and_parse_and_score::{read_lines, ParseAndScoreRoundError};
+use crate::round::Round;
+use crate::round_parser::{RoundActionParser, RoundParser};
+use crate::simulation::{play_round, PlayerStats};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::cmp::Reverse;
+use std::fmt;
+
+// Players carry state between rounds, so every match gets fresh players from
+// each entrant's factory.
+pub struct Entrant {
+    name: String,
+    make_player: Box<dyn Fn() -> Box<dyn Player>>,
+}
+
+impl Entrant {
+    pub fn new<F>(name: &str, make_player: F) -> Self
+    where
+        F: Fn() -> Box<dyn Player> + 'static,
+    {
+        Entrant {
+            name: name.to_string(),
+            make_player: Box::new(make_player),
+        }
+    }
+
+    // An entrant that replays the user actions recorded in a guide file.
+    pub fn from_guide<T>(
+        name: &str,
+        filename: &str,
+        parser: RoundParser<T>,
+    ) -> Result<Self, ParseAndScoreRoundError>
+    where
+        T: RoundActionParser,
+    {
+        let mut rounds: Vec<Round> = Vec::new();
+
+        for line in read_lines(filename)? {
+            rounds.push(parser.parse(line?)?);
+        }
+
+        let actions: Vec<_> = rounds.iter().map(|round| round.user_action).collect();
+
+        Ok(Self::new(name, move || {
+            Box::new(ScriptedPlayer::new(actions.clone()))
+        }))
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct MatchConfig {
+    // A match ends once either side has won a majority of `best_of` rounds.
+    pub best_of: usize,
+    // Drawn rounds don't count towards `best_of`, so this caps how long a
+    // match can run. A match that hits the cap without a majority is decided
+    // on rounds won, or drawn if those are level.
+    pub max_rounds: usize,
+    pub seed: u64,
+}
+
+impl Default for MatchConfig {
+    fn default() -> Self {
+        MatchConfig {
+            best_of: 5,
+            max_rounds: 50,
+            seed: 0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MatchResult {
+    FirstWins,
+    SecondWins,
+    Draw,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MatchRecord {
+    pub first: String,
+    pub second: String,
+    pub first_stats: PlayerStats,
+    pub second_stats: PlayerStats,
+    pub result: MatchResult,
+}
+
+impl MatchRecord {
+    pub fn winner(&self) -> Option<&str> {
+        match self.result {
+            MatchResult::FirstWins => Some(&self.first),
+            MatchResult::SecondWins => Some(&self.second),
+            MatchResult::Draw => None,
+        }
+    }
+}
+
+pub fn play_match(
+    first: &Entrant,
+    second: &Entrant,
+    config: &MatchConfig,
+    seed: u64,
+) -> MatchRecord {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut first_player = (first.make_player)();
+    let mut second_player = (second.make_player)();
+
+    let wins_needed = config.best_of / 2 + 1;
+    let mut first_stats = PlayerStats::default();
+    let mut second_stats = PlayerStats::default();
+
+    while first_stats.wins < wins_needed
+        && second_stats.wins < wins_needed
+        && first_stats.rounds() < config.max_rounds
+    {
+        play_round(
+            first_player.as_mut(),
+            second_player.as_mut(),
+            &mut rng,
+            &mut first_stats,
+            &mut second_stats,
+        );
+    }
+
+    let result = match first_stats.wins.cmp(&second_stats.wins) {
+        std::cmp::Ordering::Greater => MatchResult::FirstWins,
+        std::cmp::Ordering::Less => MatchResult::SecondWins,
+        std::cmp::Ordering::Equal => MatchResult::Draw,
+    };
+
+    MatchRecord {
+        first: first.name.clone(),
+        second: second.name.clone(),
+        first_stats,
+        second_stats,
+        result,
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Standing {
+    pub name: String,
+    pub played: usize,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+    pub total_score: i32,
+}
+
+impl Standing {
+    // Three points for a win, one for a draw.
+    pub fn points(&self) -> usize {
+        self.wins * 3 + self.draws
+    }
+}
+
+#[derive(Debug)]
+pub struct TournamentResult {
+    pub matches: Vec<MatchRecord>,
+    pub standings: Vec<Standing>,
+    pub champion: Option<String>,
+}
+
+impl fmt::Display for TournamentResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name_width = self
+            .standings
+            .iter()
+            .map(|standing| standing.name.len())
+            .max()
+            .unwrap_or(0)
+            .max("Name".len());
+
+        writeln!(
+            f,
+            "{:>3}  {:<name_width$}  {:>3} {:>3} {:>3} {:>3} {:>4} {:>6}",
+            "Pos", "Name", "P", "W", "D", "L", "Pts", "Score"
+        )?;
+
+        for (i, standing) in self.standings.iter().enumerate() {
+            writeln!(
+                f,
+                "{:>3}  {:<name_width$}  {:>3} {:>3} {:>3} {:>3} {:>4} {:>6}",
+                i + 1,
+                standing.name,
+                standing.played,
+                standing.wins,
+                standing.draws,
+                standing.losses,
+                standing.points(),
+                standing.total_score
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+// Every entrant plays every other entrant once.
+pub fn round_robin(entrants: &[Entrant], config: &MatchConfig) -> TournamentResult {
+    let mut matches = Vec::new();
+    let mut pairings = Vec::new();
+
+    for i in 0..entrants.len() {
+        for j in i + 1..entrants.len() {
+            let seed = config.seed.wrapping_add(matches.len() as u64);
+            matches.push(play_match(&entrants[i], &entrants[j], config, seed));
+            pairings.push((i, j));
+        }
+    }
+
+    let mut standings = tally(entrants, &matches, &pairings);
+    standings.sort_by_key(|standing| (Reverse(standing.points()), Reverse(standing.total_score)));
+
+    let champion = standings.first().map(|standing| standing.name.clone());
+
+    TournamentResult {
+        matches,
+        standings,
+        champion,
+    }
+}
+
+// Entrants are paired off in order each round, with a bye for the last
+// entrant when the count is odd. A drawn match goes to whoever scored more,
+// and then to the earlier entrant.
+pub fn single_elimination(entrants: &[Entrant], config: &MatchConfig) -> TournamentResult {
+    let mut matches = Vec::new();
+    let mut pairings = Vec::new();
+    let mut stage_reached = vec![0; entrants.len()];
+    let mut remaining: Vec<usize> = (0..entrants.len()).collect();
+    let mut stage = 0;
+
+    while remaining.len() > 1 {
+        stage += 1;
+        let mut advancing = Vec::new();
+
+        for pair in remaining.chunks(2) {
+            let (first, second) = match pair {
+                [first, second] => (*first, *second),
+                _ => {
+                    advancing.push(pair[0]);
+                    continue;
+                }
+            };
+
+            let seed = config.seed.wrapping_add(matches.len() as u64);
+            let record = play_match(&entrants[first], &entrants[second], config, seed);
+
+            let winner = match record.result {
+                MatchResult::FirstWins => first,
+                MatchResult::SecondWins => second,
+                MatchResult::Draw
+                    if record.second_stats.total_score > record.first_stats.total_score =>
+                {
+                    second
+                }
+                MatchResult::Draw => first,
+            };
+
+            advancing.push(winner);
+            matches.push(record);
+            pairings.push((first, second));
+        }
+
+        for &i in &advancing {
+            stage_reached[i] = stage;
+        }
+
+        remaining = advancing;
+    }
+
+    let mut standings = tally(entrants, &matches, &pairings);
+    let mut order: Vec<usize> = (0..entrants.len()).collect();
+    order.sort_by_key(|&i| {
+        (
+            Reverse(stage_reached[i]),
+            Reverse(standings[i].points()),
+            Reverse(standings[i].total_score),
+        )
+    });
+    standings = order.into_iter().map(|i| standings[i].clone()).collect();
+
+    let champion = remaining.first().map(|&i| entrants[i].name.clone());
+
+    TournamentResult {
+        matches,
+        standings,
+        champion,
+    }
+}
+
+// Standings in entrant order. `pairings` holds the entrant indices behind
+// each match, since names needn't be unique.
+fn tally(
+    entrants: &[Entrant],
+    matches: &[MatchRecord],
+    pairings: &[(usize, usize)],
+) -> Vec<Standing> {
+    let mut standings: Vec<Standing> = entrants
+        .iter()
+        .map(|entrant| Standing {
+            name: entrant.name.clone(),
+            ..Standing::default()
+        })
+        .collect();
+
+    for (record, &(first, second)) in matches.iter().zip(pairings) {
+        for (i, stats, result) in [
+            (first, &record.first_stats, MatchResult::FirstWins),
+            (second, &record.second_stats, MatchResult::SecondWins),
+        ] {
+            let standing = &mut standings[i];
+
+            standing.played += 1;
+            standing.total_score += stats.total_score;
+
+            if record.result == result {
+                standing.wins += 1;
+            } else if record.result == MatchResult::Draw {
+                standing.draws += 1;
+            } else {
+                standing.losses += 1;
+            }
+        }
+    }
+
+    standings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::{ConstantPlayer, MarkovChainPlayer};
+    use crate::round::RoundAction;
+
+    fn constant(action: RoundAction) -> Entrant {
+        let name = format!("always-{:?}", action).to_lowercase();
+
+        Entrant::new(&name, move || Box::new(ConstantPlayer::new(action)))
+    }
+
+    #[test]
+    fn test_play_match_best_of() {
+        let config = MatchConfig::default();
+        let record = play_match(
+            &constant(RoundAction::Rock),
+            &constant(RoundAction::Paper),
+            &config,
+            0,
+        );
+
+        assert_eq!(record.result, MatchResult::SecondWins);
+        assert_eq!(record.winner(), Some("always-paper"));
+        assert_eq!(record.second_stats.wins, 3);
+        assert_eq!(record.first_stats.rounds(), 3);
+    }
+
+    #[test]
+    fn test_play_match_draw() {
+        let config = MatchConfig::default();
+        let record = play_match(
+            &constant(RoundAction::Rock),
+            &constant(RoundAction::Rock),
+            &config,
+            0,
+        );
+
+        assert_eq!(record.result, MatchResult::Draw);
+        assert_eq!(record.first_stats.draws, config.max_rounds);
+    }
+
+    #[test]
+    fn test_round_robin() {
+        let entrants = vec![
+            constant(RoundAction::Rock),
+            constant(RoundAction::Paper),
+            constant(RoundAction::Scissors),
+        ];
+
+        let result = round_robin(&entrants, &MatchConfig::default());
+
+        assert_eq!(result.matches.len(), 3);
+        for standing in &result.standings {
+            assert_eq!((standing.wins, standing.losses), (1, 1));
+            assert_eq!(standing.points(), 3);
+        }
+    }
+
+    #[test]
+    fn test_single_elimination_with_bye() {
+        let entrants = vec![
+            constant(RoundAction::Rock),
+            constant(RoundAction::Scissors),
+            Entrant::new("markov-chain", || Box::new(MarkovChainPlayer::new())),
+        ];
+
+        let result = single_elimination(&entrants, &MatchConfig::default());
+
+        assert_eq!(result.matches.len(), 2);
+        assert_eq!(result.matches[0].winner(), Some("always-rock"));
+        assert_eq!(result.champion.as_deref(), Some("markov-chain"));
+        assert_eq!(result.standings[0].name, "markov-chain");
+        assert_eq!(result.standings[2].name, "always-scissors");
+    }
+
+    #[test]
+    fn test_duplicate_names() {
+        let entrants = vec![
+            Entrant::new("player", || {
+                Box::new(ConstantPlayer::new(RoundAction::Rock))
+            }),
+            Entrant::new("player", || {
+                Box::new(ConstantPlayer::new(RoundAction::Paper))
+            }),
+            constant(RoundAction::Scissors),
+        ];
+
+        let result = round_robin(&entrants, &MatchConfig::default());
+        for standing in &result.standings {
+            assert_eq!(standing.played, 2);
+            assert_eq!((standing.wins, standing.losses), (1, 1));
+        }
+
+        let result = single_elimination(&entrants, &MatchConfig::default());
+        // Paper beats rock, then loses the final to scissors after its bye.
+        let records: Vec<(usize, usize)> = result
+            .standings
+            .iter()
+            .map(|standing| (standing.played, standing.wins))
+            .collect();
+        assert_eq!(records, vec![(1, 1), (2, 1), (1, 0)]);
+        assert_eq!(result.standings[0].name, "always-scissors");
+    }
+
+    #[test]
+    fn test_entrant_from_guide() {
+        let guide =
+            Entrant::from_guide("guide", "fixtures/rounds.txt", RoundParser::default()).unwrap();
+        let record = play_match(
+            &guide,
+            &constant(RoundAction::Rock),
+            &MatchConfig::default(),
+            0,
+        );
+
+        // The guide cycles through paper, rock, scissors.
+        assert_eq!(record.result, MatchResult::FirstWins);
+        assert_eq!(record.first_stats.wins, 3);
+        assert_eq!(record.first_stats.draws, 2);
+        assert_eq!(record.first_stats.losses, 2);
+    }
+
+    #[test]
+    fn test_standings_table() {
+        let entrants = vec![constant(RoundAction::Rock), constant(RoundAction::Paper)];
+
+        let table = round_robin(&entrants, &MatchConfig::default()).to_string();
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].contains("always-paper"));
+        assert!(lines[2].contains("always-rock"));
+    }
+}
@@ -0,0 +1,93 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use day03::badge_groups::discover_badge_groups;
+use day03::item_set::ItemSet;
+use day03::rucksack::RucksackPair;
+use day03::{read_and_prioritize_rucksacks_by_group, PriorityTable};
+
+fn rucksack_lines() -> Vec<Vec<char>> {
+    std::fs::read_to_string("input.txt")
+        .unwrap()
+        .lines()
+        .map(|line| line.chars().collect())
+        .collect()
+}
+
+// The nested Vec::contains scan that ItemSet replaced.
+fn common_items_by_scan(groups: &[&[char]]) -> Vec<char> {
+    let mut common = Vec::new();
+
+    for item in groups[0] {
+        if groups[1..].iter().all(|group| group.contains(item)) && !common.contains(item) {
+            common.push(*item);
+        }
+    }
+
+    common
+}
+
+fn common_items_by_set(groups: &[ItemSet]) -> ItemSet {
+    groups[1..].iter().fold(groups[0].clone(), |common, group| {
+        common.intersection(group)
+    })
+}
+
+fn bench_common_items(c: &mut Criterion) {
+    let lines = rucksack_lines();
+    let sets: Vec<ItemSet> = lines.iter().map(|line| ItemSet::from_items(line)).collect();
+
+    let mut group = c.benchmark_group("group common items");
+
+    group.bench_function("vec scan", |b| {
+        b.iter(|| {
+            for chunk in lines.chunks_exact(3) {
+                let groups: Vec<&[char]> = chunk.iter().map(|line| line.as_slice()).collect();
+                black_box(common_items_by_scan(&groups));
+            }
+        })
+    });
+
+    group.bench_function("item set", |b| {
+        b.iter(|| {
+            for chunk in sets.chunks_exact(3) {
+                black_box(common_items_by_set(chunk));
+            }
+        })
+    });
+
+    group.finish();
+}
+
+fn bench_read_and_prioritize(c: &mut Criterion) {
+    let priorities = PriorityTable::default();
+
+    c.bench_function("read and prioritize by group", |b| {
+        b.iter(|| {
+            read_and_prioritize_rucksacks_by_group(black_box("input.txt"), 3, &priorities).unwrap()
+        })
+    });
+}
+
+fn bench_discover_badge_groups(c: &mut Criterion) {
+    let lines: Vec<String> = std::fs::read_to_string("input.txt")
+        .unwrap()
+        .lines()
+        .map(String::from)
+        .collect();
+
+    // Shuffled, so the groups aren't simply consecutive lines.
+    let pairs: Vec<RucksackPair> = (0..lines.len())
+        .map(|i| RucksackPair::try_from(lines[i * 7 % lines.len()].clone()).unwrap())
+        .collect();
+
+    c.bench_function("discover shuffled badge groups", |b| {
+        b.iter(|| discover_badge_groups(black_box(&pairs), 3).unwrap())
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_common_items,
+    bench_read_and_prioritize,
+    bench_discover_badge_groups
+);
+criterion_main!(benches);
@@ -0,0 +1,344 @@
+use crate::item::Item;
+use crate::item_set::ItemSet;
+use crate::rucksack::RucksackPair;
+use crate::rucksack_reader::{RucksackReader, RucksackReaderError};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum DiscoverBadgeGroupsError {
+    #[error("Error reading file: {0}")]
+    RucksackReaderError(#[from] RucksackReaderError),
+    #[error("Invalid group size: {0}")]
+    InvalidGroupSizeError(usize),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BadgeGroup {
+    // Indexes into the rucksack pairs the group was discovered from.
+    pub members: Vec<usize>,
+    pub badge: Item,
+}
+
+// Partitions rucksacks given in no particular order into groups of
+// `group_size` that each share exactly one item. Returns None when no such
+// partition exists.
+//
+// This is an exact cover search. Every possible group is listed up front,
+// then each step takes the ungrouped rucksack with the fewest groups still
+// open to it and tries each of those, backtracking on dead ends. Choosing a
+// group closes every other group sharing a member, so a rucksack left with no
+// open group is found, and the branch abandoned, straight away.
+pub fn discover_badge_groups(
+    pairs: &[RucksackPair],
+    group_size: usize,
+) -> Result<Option<Vec<BadgeGroup>>, DiscoverBadgeGroupsError> {
+    if group_size == 0 {
+        return Err(DiscoverBadgeGroupsError::InvalidGroupSizeError(group_size));
+    }
+
+    if !pairs.len().is_multiple_of(group_size) {
+        return Ok(None);
+    }
+
+    let item_sets: Vec<ItemSet> = pairs.iter().map(RucksackPair::get_item_set).collect();
+    let mut groups = Vec::new();
+
+    for first in 0..item_sets.len() {
+        list_groups(
+            &item_sets,
+            group_size,
+            &mut vec![first],
+            item_sets[first].clone(),
+            &mut groups,
+        );
+    }
+
+    let mut groups_of = vec![Vec::new(); pairs.len()];
+    for (group, members) in groups.chunks(group_size).enumerate() {
+        for &member in members {
+            groups_of[member].push(group);
+        }
+    }
+
+    let mut search = Search {
+        group_size,
+        open_counts: groups_of.iter().map(Vec::len).collect(),
+        open: vec![true; groups.len() / group_size],
+        groups,
+        groups_of,
+        grouped: vec![false; pairs.len()],
+        chosen: Vec::new(),
+    };
+
+    if !search.cover() {
+        return Ok(None);
+    }
+
+    Ok(Some(
+        search
+            .chosen
+            .iter()
+            .map(|&group| {
+                let members = search.members(group).to_vec();
+                let badge = members
+                    .iter()
+                    .map(|&member| item_sets[member].clone())
+                    .reduce(|common, items| common.intersection(&items))
+                    .unwrap()
+                    .items()
+                    .remove(0);
+
+                BadgeGroup { members, badge }
+            })
+            .collect(),
+    ))
+}
+
+// Appends to `groups` the members of every group that extends `members` with
+// later rucksacks and shares exactly one item. Extensions stop as soon as the
+// members have nothing left in common.
+fn list_groups(
+    item_sets: &[ItemSet],
+    group_size: usize,
+    members: &mut Vec<usize>,
+    common_items: ItemSet,
+    groups: &mut Vec<usize>,
+) {
+    if members.len() == group_size {
+        if common_items.len() == 1 {
+            groups.extend(members.iter());
+        }
+
+        return;
+    }
+
+    for candidate in members.last().unwrap() + 1..item_sets.len() {
+        let candidate_common_items = common_items.intersection(&item_sets[candidate]);
+        if candidate_common_items.is_empty() {
+            continue;
+        }
+
+        members.push(candidate);
+        list_groups(
+            item_sets,
+            group_size,
+            members,
+            candidate_common_items,
+            groups,
+        );
+        members.pop();
+    }
+}
+
+pub fn read_and_discover_badge_groups(
+    filename: &str,
+    group_size: usize,
+) -> Result<Option<Vec<BadgeGroup>>, DiscoverBadgeGroupsError> {
+    let rucksack_pairs = RucksackReader::read_from_file(filename)?;
+
+    discover_badge_groups(&rucksack_pairs, group_size)
+}
+
+struct Search {
+    group_size: usize,
+    // The members of every possible group, `group_size` at a time.
+    groups: Vec<usize>,
+    // For each rucksack, the groups it could join.
+    groups_of: Vec<Vec<usize>>,
+    // Whether each group is still available, and how many available groups
+    // each rucksack has.
+    open: Vec<bool>,
+    open_counts: Vec<usize>,
+    grouped: Vec<bool>,
+    chosen: Vec<usize>,
+}
+
+impl Search {
+    fn cover(&mut self) -> bool {
+        let Some(rucksack) = (0..self.grouped.len())
+            .filter(|&rucksack| !self.grouped[rucksack])
+            .min_by_key(|&rucksack| self.open_counts[rucksack])
+        else {
+            return true;
+        };
+
+        let candidates: Vec<usize> = self.groups_of[rucksack]
+            .iter()
+            .copied()
+            .filter(|&group| self.open[group])
+            .collect();
+
+        for group in candidates {
+            let closed = self.choose(group);
+
+            if self.cover() {
+                return true;
+            }
+
+            self.unchoose(group, closed);
+        }
+
+        false
+    }
+
+    // Puts the group's members in it and closes every group that shares one
+    // of them. Returns the closed groups so the choice can be undone.
+    fn choose(&mut self, group: usize) -> Vec<usize> {
+        let mut closed = Vec::new();
+
+        for i in 0..self.group_size {
+            let member = self.groups[group * self.group_size + i];
+            self.grouped[member] = true;
+
+            for j in 0..self.groups_of[member].len() {
+                let other = self.groups_of[member][j];
+                if self.open[other] {
+                    self.set_open(other, false);
+                    closed.push(other);
+                }
+            }
+        }
+
+        self.chosen.push(group);
+
+        closed
+    }
+
+    fn unchoose(&mut self, group: usize, closed: Vec<usize>) {
+        self.chosen.pop();
+
+        for other in closed {
+            self.set_open(other, true);
+        }
+
+        for i in 0..self.group_size {
+            let member = self.groups[group * self.group_size + i];
+            self.grouped[member] = false;
+        }
+    }
+
+    fn set_open(&mut self, group: usize, open: bool) {
+        self.open[group] = open;
+
+        for i in 0..self.group_size {
+            let member = self.groups[group * self.group_size + i];
+
+            if open {
+                self.open_counts[member] += 1;
+            } else {
+                self.open_counts[member] -= 1;
+            }
+        }
+    }
+
+    fn members(&self, group: usize) -> &[usize] {
+        &self.groups[group * self.group_size..(group + 1) * self.group_size]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(lines: &[&str]) -> Vec<RucksackPair> {
+        lines
+            .iter()
+            .map(|line| RucksackPair::try_from(line.to_string()).unwrap())
+            .collect()
+    }
+
+    fn assert_valid_partition(pairs: &[RucksackPair], groups: &[BadgeGroup]) {
+        let mut members: Vec<usize> = groups
+            .iter()
+            .flat_map(|group| group.members.clone())
+            .collect();
+        members.sort();
+        assert_eq!(members, (0..pairs.len()).collect::<Vec<_>>());
+
+        for group in groups {
+            let common_items = group
+                .members
+                .iter()
+                .map(|&i| pairs[i].get_item_set())
+                .reduce(|common, items| common.intersection(&items))
+                .unwrap();
+            assert_eq!(common_items.items(), vec![group.badge.clone()]);
+        }
+    }
+
+    #[test]
+    fn test_discover_badge_groups_shuffled() {
+        // The puzzle example with its two groups interleaved.
+        let pairs = pairs(&[
+            "wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn",
+            "vJrwpWtwJgWrhcsFMMfFFhFp",
+            "ttgJtRGJQctTZtZT",
+            "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL",
+            "CrZsJsPPZsGzwwsLwLmpwMDw",
+            "PmmdzqPrVvPwwTWBwg",
+        ]);
+
+        let groups = discover_badge_groups(&pairs, 3).unwrap().unwrap();
+
+        // Besides the puzzle's own groups, {0, 3, 5} share only 'q' and
+        // {1, 2, 4} only 'J'. Either partition is a valid answer.
+        assert_valid_partition(&pairs, &groups);
+    }
+
+    #[test]
+    fn test_discover_badge_groups_needs_backtracking() {
+        // 0 and 1 share only 'a', but pairing them strands 2 and 3.
+        let pairs = pairs(&["ab", "ac", "bd", "ce"]);
+
+        let groups = discover_badge_groups(&pairs, 2).unwrap().unwrap();
+
+        assert_eq!(
+            groups
+                .iter()
+                .map(|group| group.badge.clone())
+                .collect::<Vec<_>>(),
+            vec!['b', 'c']
+        );
+    }
+
+    #[test]
+    fn test_discover_badge_groups_impossible() {
+        assert_eq!(
+            discover_badge_groups(&pairs(&["ab", "cd"]), 2).unwrap(),
+            None
+        );
+        assert_eq!(
+            discover_badge_groups(&pairs(&["ab", "ab"]), 2).unwrap(),
+            None
+        );
+        assert_eq!(discover_badge_groups(&pairs(&["ab"]), 2).unwrap(), None);
+    }
+
+    #[test]
+    fn test_discover_badge_groups_shuffled_puzzle_input() {
+        let lines: Vec<String> = std::fs::read_to_string("input.txt")
+            .unwrap()
+            .lines()
+            .map(String::from)
+            .collect();
+
+        // 7 is coprime to the line count, so this visits every line once.
+        assert_ne!(lines.len() % 7, 0);
+        let shuffled: Vec<RucksackPair> = (0..lines.len())
+            .map(|i| RucksackPair::try_from(lines[i * 7 % lines.len()].clone()).unwrap())
+            .collect();
+
+        let groups = discover_badge_groups(&shuffled, 3).unwrap().unwrap();
+
+        assert_valid_partition(&shuffled, &groups);
+    }
+
+    #[test]
+    fn test_read_and_discover_badge_groups() {
+        let groups = read_and_discover_badge_groups("fixtures/rucksacks.txt", 3)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(groups.len(), 2);
+    }
+}
@@ -0,0 +1,170 @@
+use crate::item::Item;
+use crate::item_set::ItemSet;
+use crate::prioritizer::{PrioritizationError, PriorityTable};
+use crate::rucksack::RucksackPair;
+use crate::rucksack_reader::{RucksackReader, RucksackReaderError};
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum InventoryError {
+    #[error("Error reading file: {0}")]
+    RucksackReaderError(#[from] RucksackReaderError),
+    #[error("Error prioritizing item: {0}")]
+    PrioritizationError(#[from] PrioritizationError),
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CompartmentFrequencies {
+    pub left: BTreeMap<Item, usize>,
+    pub right: BTreeMap<Item, usize>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct InventoryReport {
+    pub rucksack_count: usize,
+    pub frequencies: CompartmentFrequencies,
+    pub most_duplicated_items: Vec<(Item, usize)>,
+    pub priority_distribution: BTreeMap<i32, usize>,
+    pub items_in_every_rucksack: Vec<Item>,
+}
+
+impl InventoryReport {
+    pub fn new(
+        pairs: &[RucksackPair],
+        priorities: &PriorityTable,
+    ) -> Result<Self, PrioritizationError> {
+        Ok(InventoryReport {
+            rucksack_count: pairs.len(),
+            frequencies: item_frequencies(pairs),
+            most_duplicated_items: most_duplicated_items(pairs),
+            priority_distribution: priority_distribution(pairs, priorities)?,
+            items_in_every_rucksack: items_in_every_rucksack(pairs).items(),
+        })
+    }
+}
+
+pub fn read_inventory_report(
+    filename: &str,
+    priorities: &PriorityTable,
+) -> Result<InventoryReport, InventoryError> {
+    let rucksack_pairs = RucksackReader::read_from_file(filename)?;
+
+    Ok(InventoryReport::new(&rucksack_pairs, priorities)?)
+}
+
+// How many of each item there are in all left and all right compartments.
+pub fn item_frequencies(pairs: &[RucksackPair]) -> CompartmentFrequencies {
+    let mut frequencies = CompartmentFrequencies::default();
+
+    for pair in pairs {
+        let (left, right) = pair.get_rucksacks();
+
+        for item in left.get_items() {
+            *frequencies.left.entry(item.clone()).or_default() += 1;
+        }
+
+        for item in right.get_items() {
+            *frequencies.right.entry(item.clone()).or_default() += 1;
+        }
+    }
+
+    frequencies
+}
+
+// For each item found in both compartments of at least one rucksack, the
+// number of rucksacks it's duplicated in, most duplicated first.
+pub fn most_duplicated_items(pairs: &[RucksackPair]) -> Vec<(Item, usize)> {
+    let mut counts: BTreeMap<Item, usize> = BTreeMap::new();
+
+    for item in pairs.iter().flat_map(shared_items) {
+        *counts.entry(item).or_default() += 1;
+    }
+
+    let mut duplicated: Vec<(Item, usize)> = counts.into_iter().collect();
+    duplicated.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+    duplicated
+}
+
+// How many duplicated items there are at each priority.
+pub fn priority_distribution(
+    pairs: &[RucksackPair],
+    priorities: &PriorityTable,
+) -> Result<BTreeMap<i32, usize>, PrioritizationError> {
+    let mut distribution = BTreeMap::new();
+
+    for item in pairs.iter().flat_map(shared_items) {
+        *distribution
+            .entry(priorities.get_priority(&item)?)
+            .or_default() += 1;
+    }
+
+    Ok(distribution)
+}
+
+pub fn items_in_every_rucksack(pairs: &[RucksackPair]) -> ItemSet {
+    match pairs.split_first() {
+        Some((first, others)) => others
+            .iter()
+            .fold(first.get_item_set(), |common_items, other| {
+                common_items.intersection(&other.get_item_set())
+            }),
+        None => ItemSet::new(),
+    }
+}
+
+fn shared_items(pair: &RucksackPair) -> Vec<Item> {
+    let (left, right) = pair.get_rucksacks();
+
+    left.get_item_set()
+        .intersection(right.get_item_set())
+        .items()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_inventory_report() {
+        let report =
+            read_inventory_report("fixtures/rucksacks.txt", &PriorityTable::default()).unwrap();
+
+        assert_eq!(report.rucksack_count, 6);
+        assert_eq!(report.frequencies.left[&Item::Char('v')], 3);
+        assert_eq!(report.frequencies.right[&Item::Char('v')], 3);
+        assert_eq!(report.most_duplicated_items.len(), 6);
+        assert_eq!(report.priority_distribution.values().sum::<usize>(), 6);
+        assert_eq!(report.priority_distribution[&16], 1);
+        assert!(report.items_in_every_rucksack.is_empty());
+    }
+
+    #[test]
+    fn test_most_duplicated_items() {
+        let pairs: Vec<RucksackPair> = ["abac", "abab", "xyxz"]
+            .iter()
+            .map(|line| RucksackPair::try_from(line.to_string()).unwrap())
+            .collect();
+
+        assert_eq!(
+            most_duplicated_items(&pairs),
+            vec![
+                (Item::Char('a'), 2),
+                (Item::Char('b'), 1),
+                (Item::Char('x'), 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_items_in_every_rucksack() {
+        let pairs: Vec<RucksackPair> = ["abcd", "dxay", "zzad"]
+            .iter()
+            .map(|line| RucksackPair::try_from(line.to_string()).unwrap())
+            .collect();
+
+        assert_eq!(items_in_every_rucksack(&pairs).items(), vec!['a', 'd']);
+        assert!(items_in_every_rucksack(&[]).is_empty());
+    }
+}
@@ -0,0 +1,126 @@
+use std::fmt;
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
+
+// A single rucksack item: one user-perceived character. Most items are a
+// single char, but an item can also be a grapheme cluster such as a letter
+// with a combining accent or a multi-codepoint emoji. Single-char items are
+// always stored as Char, and every item is in NFC, so a precomposed 'é' and
+// an 'e' followed by a combining accent are the same item.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Item {
+    Char(char),
+    Cluster(Box<str>),
+}
+
+impl Item {
+    pub fn as_char(&self) -> Option<char> {
+        match self {
+            Item::Char(c) => Some(*c),
+            Item::Cluster(_) => None,
+        }
+    }
+}
+
+// Splits a line into items. ASCII lines are already normalized and can't
+// contain multi-char graphemes, so they skip both steps entirely.
+pub fn parse_items(s: &str) -> Vec<Item> {
+    if s.is_ascii() {
+        return s.bytes().map(|b| Item::Char(b as char)).collect();
+    }
+
+    let normalized: String = s.nfc().collect();
+    normalized
+        .graphemes(true)
+        .map(Item::from_normalized)
+        .collect()
+}
+
+impl From<char> for Item {
+    fn from(c: char) -> Self {
+        Item::Char(c)
+    }
+}
+
+impl From<&str> for Item {
+    fn from(s: &str) -> Self {
+        Item::from_normalized(&s.nfc().collect::<String>())
+    }
+}
+
+impl Item {
+    fn from_normalized(s: &str) -> Self {
+        let mut chars = s.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Item::Char(c),
+            _ => Item::Cluster(s.into()),
+        }
+    }
+}
+
+impl PartialEq<char> for Item {
+    fn eq(&self, other: &char) -> bool {
+        self.as_char() == Some(*other)
+    }
+}
+
+impl fmt::Display for Item {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Item::Char(c) => write!(f, "{}", c),
+            Item::Cluster(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+// Debug output matches char and str, so errors listing items read the same as
+// they did when items were plain chars.
+impl fmt::Debug for Item {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Item::Char(c) => write!(f, "{:?}", c),
+            Item::Cluster(s) => write!(f, "{:?}", s),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_items_ascii() {
+        assert_eq!(parse_items("aB"), vec![Item::Char('a'), Item::Char('B')]);
+    }
+
+    #[test]
+    fn test_parse_items_grapheme_clusters() {
+        // An x with a combining acute accent, which has no precomposed form,
+        // and a family emoji joined with zero width joiners.
+        let items = parse_items("ax\u{301}👨‍👩‍👧z");
+
+        assert_eq!(
+            items,
+            vec![
+                Item::Char('a'),
+                Item::Cluster("x\u{301}".into()),
+                Item::Cluster("👨‍👩‍👧".into()),
+                Item::Char('z'),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_normal_forms_are_equal() {
+        assert_eq!(Item::from("e\u{301}"), Item::Char('\u{e9}'));
+        assert_eq!(parse_items("e\u{301}"), parse_items("\u{e9}"));
+        assert_eq!(parse_items("e\u{301}"), vec![Item::Char('\u{e9}')]);
+    }
+
+    #[test]
+    fn test_single_char_cluster_is_char() {
+        assert_eq!(Item::from("é"), Item::Char('é'));
+        assert_eq!(format!("{:?}", Item::from("é")), "'é'");
+    }
+}
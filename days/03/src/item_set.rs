@@ -0,0 +1,175 @@
+use crate::item::Item;
+use std::collections::BTreeSet;
+
+// A set of rucksack items. Items from the standard a-z, A-Z alphabet are kept
+// as bits in a mask, so intersections are a single AND. Anything else falls
+// back to a general set. Each set has exactly one representation, so sets can
+// be compared directly.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ItemSet {
+    Mask(u64),
+    General(BTreeSet<Item>),
+}
+
+impl ItemSet {
+    pub fn new() -> Self {
+        ItemSet::Mask(0)
+    }
+
+    pub fn from_items<T>(items: &[T]) -> Self
+    where
+        T: Clone + Into<Item>,
+    {
+        let mut set = Self::new();
+
+        for item in items {
+            set.insert(item.clone().into());
+        }
+
+        set
+    }
+
+    pub fn insert(&mut self, item: Item) {
+        match (self, mask_bit(&item)) {
+            (ItemSet::Mask(mask), Some(bit)) => *mask |= 1 << bit,
+            (ItemSet::General(set), _) => {
+                set.insert(item);
+            }
+            (this, None) => {
+                let mut set: BTreeSet<Item> = this.items().into_iter().collect();
+                set.insert(item);
+                *this = ItemSet::General(set);
+            }
+        }
+    }
+
+    pub fn contains(&self, item: &Item) -> bool {
+        match self {
+            ItemSet::Mask(mask) => mask_bit(item).is_some_and(|bit| mask & (1 << bit) != 0),
+            ItemSet::General(set) => set.contains(item),
+        }
+    }
+
+    pub fn intersection(&self, other: &ItemSet) -> ItemSet {
+        match (self, other) {
+            (ItemSet::Mask(left), ItemSet::Mask(right)) => ItemSet::Mask(left & right),
+            _ => Self::from_set(
+                self.items()
+                    .into_iter()
+                    .filter(|item| other.contains(item))
+                    .collect(),
+            ),
+        }
+    }
+
+    pub fn union(&self, other: &ItemSet) -> ItemSet {
+        match (self, other) {
+            (ItemSet::Mask(left), ItemSet::Mask(right)) => ItemSet::Mask(left | right),
+            _ => Self::from_set(self.items().into_iter().chain(other.items()).collect()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            ItemSet::Mask(mask) => mask.count_ones() as usize,
+            ItemSet::General(set) => set.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // Mask items come out in priority order, a-z then A-Z.
+    pub fn items(&self) -> Vec<Item> {
+        match self {
+            ItemSet::Mask(mask) => (0..52)
+                .filter(|bit| mask & (1 << bit) != 0)
+                .map(mask_item)
+                .collect(),
+            ItemSet::General(set) => set.iter().cloned().collect(),
+        }
+    }
+
+    fn from_set(set: BTreeSet<Item>) -> ItemSet {
+        if set.iter().all(|item| mask_bit(item).is_some()) {
+            ItemSet::Mask(
+                set.iter()
+                    .filter_map(mask_bit)
+                    .fold(0, |mask, bit| mask | 1 << bit),
+            )
+        } else {
+            ItemSet::General(set)
+        }
+    }
+}
+
+impl Default for ItemSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn mask_bit(item: &Item) -> Option<u32> {
+    match item {
+        Item::Char(c @ 'a'..='z') => Some(*c as u32 - 'a' as u32),
+        Item::Char(c @ 'A'..='Z') => Some(*c as u32 - 'A' as u32 + 26),
+        _ => None,
+    }
+}
+
+fn mask_item(bit: u32) -> Item {
+    if bit < 26 {
+        Item::Char(char::from_u32('a' as u32 + bit).unwrap())
+    } else {
+        Item::Char(char::from_u32('A' as u32 + bit - 26).unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intersection_of_masks() {
+        let left = ItemSet::from_items(&['a', 'b', 'Z']);
+        let right = ItemSet::from_items(&['b', 'c', 'Z']);
+
+        let common = left.intersection(&right);
+
+        assert!(matches!(common, ItemSet::Mask(_)));
+        assert_eq!(common.items(), vec!['b', 'Z']);
+    }
+
+    #[test]
+    fn test_falls_back_to_general_set() {
+        let left = ItemSet::from_items(&['a', '1', 'é']);
+        let right = ItemSet::from_items(&['a', 'é']);
+
+        assert!(matches!(left, ItemSet::General(_)));
+        assert!(left.contains(&Item::Char('1')));
+        assert_eq!(left.len(), 3);
+        assert_eq!(left.intersection(&right).items(), vec!['a', 'é']);
+    }
+
+    #[test]
+    fn test_grapheme_cluster_items() {
+        let cluster = Item::from("x\u{301}");
+        let left = ItemSet::from_items(&[Item::Char('a'), cluster.clone()]);
+        let right = ItemSet::from_items(std::slice::from_ref(&cluster));
+
+        assert_eq!(left.intersection(&right).items(), vec![cluster]);
+    }
+
+    #[test]
+    fn test_general_results_normalize_to_mask() {
+        let left = ItemSet::from_items(&['a', '1']);
+        let right = ItemSet::from_items(&['a', 'b']);
+
+        assert_eq!(left.intersection(&right), ItemSet::from_items(&['a']));
+        assert_eq!(
+            right.union(&ItemSet::from_items(&['c'])),
+            ItemSet::from_items(&['a', 'b', 'c'])
+        );
+    }
+}
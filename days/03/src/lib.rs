@@ -1,13 +1,20 @@
-mod prioritizer;
-mod rucksack;
-mod rucksack_reader;
+pub mod badge_groups;
+pub mod inventory;
+pub mod item;
+pub mod item_set;
+pub mod prioritizer;
+pub mod reorganizer;
+pub mod rucksack;
+pub mod rucksack_reader;
 
-use prioritizer::{get_item_priority, PrioritizationError};
-use rucksack::RucksackGetCommonItemError;
+use prioritizer::PrioritizationError;
+use rucksack::{get_group_common_item, RucksackGetCommonItemError};
 use rucksack_reader::{RucksackReader, RucksackReaderError};
 
 use thiserror::Error;
 
+pub use prioritizer::{PriorityTable, PriorityTableError};
+
 #[derive(Debug, Error)]
 pub enum ReadAndPrioritizeError {
     #[error("Error reading file: {0}")]
@@ -16,15 +23,22 @@ pub enum ReadAndPrioritizeError {
     RucksackGetRepeatedCharError(#[from] RucksackGetCommonItemError),
     #[error("Error prioritizing rucksack pair: {0}")]
     PrioritizationError(#[from] PrioritizationError),
+    #[error("Invalid group size: {0}")]
+    InvalidGroupSizeError(usize),
+    #[error("Incomplete group: expected {expected} rucksacks, found {found}")]
+    IncompleteGroupError { expected: usize, found: usize },
 }
 
-pub fn read_and_prioritize_rucksacks(filename: &str) -> Result<i32, ReadAndPrioritizeError> {
+pub fn read_and_prioritize_rucksacks(
+    filename: &str,
+    priorities: &PriorityTable,
+) -> Result<i32, ReadAndPrioritizeError> {
     let rucksack_pairs = RucksackReader::read_from_file(filename)?;
     let mut total_priority = 0;
 
     for pair in rucksack_pairs {
         let repeated_char = pair.get_common_item()?;
-        let priority = get_item_priority(&repeated_char)?;
+        let priority = priorities.get_priority(&repeated_char)?;
         total_priority += priority;
     }
 
@@ -33,18 +47,29 @@ pub fn read_and_prioritize_rucksacks(filename: &str) -> Result<i32, ReadAndPrior
 
 pub fn read_and_prioritize_rucksacks_by_group(
     filename: &str,
+    group_size: usize,
+    priorities: &PriorityTable,
 ) -> Result<i32, ReadAndPrioritizeError> {
+    if group_size == 0 {
+        return Err(ReadAndPrioritizeError::InvalidGroupSizeError(group_size));
+    }
+
     let rucksack_pairs = RucksackReader::read_from_file(filename)?;
-    let mut total_priority = 0;
+    let groups = rucksack_pairs.chunks_exact(group_size);
 
-    for pairs in rucksack_pairs.chunks(3) {
-        let first_pair = &pairs[0];
-        let second_pair = &pairs[1];
-        let third_pair = &pairs[2];
+    if !groups.remainder().is_empty() {
+        return Err(ReadAndPrioritizeError::IncompleteGroupError {
+            expected: group_size,
+            found: groups.remainder().len(),
+        });
+    }
+
+    let mut total_priority = 0;
 
-        let repeated_char = first_pair.get_common_item_with((second_pair, third_pair))?;
+    for group in groups {
+        let repeated_char = get_group_common_item(group)?;
 
-        let priority = get_item_priority(&repeated_char)?;
+        let priority = priorities.get_priority(&repeated_char)?;
         total_priority += priority;
     }
 
@@ -58,8 +83,50 @@ mod test {
     #[test]
     fn test_read_and_prioritize_rucksacks() {
         assert_eq!(
-            read_and_prioritize_rucksacks("fixtures/rucksacks.txt").unwrap(),
+            read_and_prioritize_rucksacks("fixtures/rucksacks.txt", &PriorityTable::default())
+                .unwrap(),
             157
         );
     }
+
+    #[test]
+    fn test_read_and_prioritize_rucksacks_by_group() {
+        assert_eq!(
+            read_and_prioritize_rucksacks_by_group(
+                "fixtures/rucksacks.txt",
+                3,
+                &PriorityTable::default()
+            )
+            .unwrap(),
+            70
+        );
+    }
+
+    #[test]
+    fn test_read_and_prioritize_rucksacks_with_custom_priorities() {
+        let priorities =
+            PriorityTable::from_alphabet(&('A'..='Z').chain('a'..='z').collect::<String>())
+                .unwrap();
+
+        // p, L, P, v, t, s become 42, 12, 16, 48, 46, 45.
+        assert_eq!(
+            read_and_prioritize_rucksacks("fixtures/rucksacks.txt", &priorities).unwrap(),
+            209
+        );
+    }
+
+    #[test]
+    fn test_read_and_prioritize_rucksacks_by_incomplete_group() {
+        assert!(matches!(
+            read_and_prioritize_rucksacks_by_group(
+                "fixtures/rucksacks.txt",
+                4,
+                &PriorityTable::default()
+            ),
+            Err(ReadAndPrioritizeError::IncompleteGroupError {
+                expected: 4,
+                found: 2
+            })
+        ));
+    }
 }
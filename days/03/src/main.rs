@@ -1,12 +1,14 @@
-use day03::{read_and_prioritize_rucksacks, read_and_prioritize_rucksacks_by_group};
+use day03::{read_and_prioritize_rucksacks, read_and_prioritize_rucksacks_by_group, PriorityTable};
 
 pub fn main() {
-    match read_and_prioritize_rucksacks("input.txt") {
+    let priorities = PriorityTable::default();
+
+    match read_and_prioritize_rucksacks("input.txt", &priorities) {
         Ok(priority) => println!("Total priority: {}", priority),
         Err(e) => println!("Error: {}", e),
     }
 
-    match read_and_prioritize_rucksacks_by_group("input.txt") {
+    match read_and_prioritize_rucksacks_by_group("input.txt", 3, &priorities) {
         Ok(priority) => println!("Total priority by group: {}", priority),
         Err(e) => println!("Error: {}", e),
     }
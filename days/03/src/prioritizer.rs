@@ -1,4 +1,6 @@
+use crate::item::{parse_items, Item};
 use crate::rucksack::RucksackGetCommonItemError;
+use std::collections::HashMap;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -6,17 +8,95 @@ pub enum PrioritizationError {
     #[error("Error getting repeated char: {0}")]
     RucksackGetRepeatedCharError(#[from] RucksackGetCommonItemError),
     #[error("Error getting item priority: {0}")]
-    GetItemPriorityError(char),
+    GetItemPriorityError(Item),
 }
 
-pub fn get_item_priority(item: &char) -> Result<i32, PrioritizationError> {
-    let priorities: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+#[derive(Debug, Error)]
+pub enum PriorityTableError {
+    #[error("Error reading priority file: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Invalid priority mapping on line {0}: {1}")]
+    InvalidLineError(usize, String),
+    #[error("Invalid priority on line {0}: {1}")]
+    InvalidPriorityError(usize, std::num::ParseIntError),
+    #[error("Item listed more than once: {0}")]
+    DuplicateItemError(Item),
+}
+
+// Maps items to priorities. Built once up front and then shared by every
+// lookup, rather than rebuilding the alphabet for each item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PriorityTable {
+    priorities: HashMap<Item, i32>,
+}
+
+impl PriorityTable {
+    // The first item of the alphabet has priority 1, the next 2, and so on.
+    pub fn from_alphabet(alphabet: &str) -> Result<Self, PriorityTableError> {
+        Self::from_mapping(parse_items(alphabet).into_iter().zip(1..))
+    }
+
+    pub fn from_mapping<I, T>(mapping: I) -> Result<Self, PriorityTableError>
+    where
+        I: IntoIterator<Item = (T, i32)>,
+        T: Into<Item>,
+    {
+        let mut priorities = HashMap::new();
+
+        for (item, priority) in mapping {
+            let item = item.into();
+            if priorities.insert(item.clone(), priority).is_some() {
+                return Err(PriorityTableError::DuplicateItemError(item));
+            }
+        }
+
+        Ok(PriorityTable { priorities })
+    }
+
+    // Reads a mapping file with one `<item> <priority>` pair per line. Blank
+    // lines are ignored.
+    pub fn from_mapping_file(filename: &str) -> Result<Self, PriorityTableError> {
+        let contents = std::fs::read_to_string(filename)?;
+        let mut mapping = Vec::new();
+
+        for (i, line) in contents.lines().enumerate() {
+            let line_number = i + 1;
+            let parts: Vec<&str> = line.split_whitespace().collect();
+
+            let (item, priority) = match parts.as_slice() {
+                [] => continue,
+                [item, priority] if parse_items(item).len() == 1 => (item, priority),
+                _ => {
+                    return Err(PriorityTableError::InvalidLineError(
+                        line_number,
+                        line.to_string(),
+                    ))
+                }
+            };
+
+            let priority = priority
+                .parse::<i32>()
+                .map_err(|e| PriorityTableError::InvalidPriorityError(line_number, e))?;
+
+            mapping.push((Item::from(*item), priority));
+        }
 
-    priorities
-        .iter()
-        .position(|&c| c == *item)
-        .map(|i| i as i32 + 1)
-        .ok_or(PrioritizationError::GetItemPriorityError(*item))
+        Self::from_mapping(mapping)
+    }
+
+    pub fn get_priority(&self, item: &Item) -> Result<i32, PrioritizationError> {
+        self.priorities
+            .get(item)
+            .copied()
+            .ok_or_else(|| PrioritizationError::GetItemPriorityError(item.clone()))
+    }
+}
+
+// The puzzle's priorities: a-z are 1 through 26, A-Z are 27 through 52.
+impl Default for PriorityTable {
+    fn default() -> Self {
+        Self::from_mapping(('a'..='z').chain('A'..='Z').zip(1..)).unwrap()
+    }
 }
 
 #[cfg(test)]
@@ -25,9 +105,38 @@ pub mod test {
 
     #[test]
     fn test_get_item_priority() {
-        assert_eq!(get_item_priority(&'a').unwrap(), 1);
-        assert_eq!(get_item_priority(&'z').unwrap(), 26);
-        assert_eq!(get_item_priority(&'A').unwrap(), 27);
-        assert_eq!(get_item_priority(&'Z').unwrap(), 52);
+        let priorities = PriorityTable::default();
+
+        assert_eq!(priorities.get_priority(&'a'.into()).unwrap(), 1);
+        assert_eq!(priorities.get_priority(&'z'.into()).unwrap(), 26);
+        assert_eq!(priorities.get_priority(&'A'.into()).unwrap(), 27);
+        assert_eq!(priorities.get_priority(&'Z'.into()).unwrap(), 52);
+        assert!(priorities.get_priority(&'1'.into()).is_err());
+    }
+
+    #[test]
+    fn test_from_alphabet() {
+        let priorities = PriorityTable::from_alphabet("0123456789").unwrap();
+
+        assert_eq!(priorities.get_priority(&'0'.into()).unwrap(), 1);
+        assert_eq!(priorities.get_priority(&'9'.into()).unwrap(), 10);
+        assert!(matches!(
+            PriorityTable::from_alphabet("abca"),
+            Err(PriorityTableError::DuplicateItemError(Item::Char('a')))
+        ));
+    }
+
+    #[test]
+    fn test_from_mapping_file() {
+        let priorities = PriorityTable::from_mapping_file("fixtures/priorities.txt").unwrap();
+
+        assert_eq!(priorities.get_priority(&'a'.into()).unwrap(), 1);
+        assert_eq!(priorities.get_priority(&'7'.into()).unwrap(), 70);
+        assert_eq!(priorities.get_priority(&'é'.into()).unwrap(), 100);
+        assert_eq!(priorities.get_priority(&'🎁'.into()).unwrap(), 1000);
+        assert_eq!(
+            priorities.get_priority(&Item::from("e\u{301}")).unwrap(),
+            100
+        );
     }
 }
@@ -0,0 +1,307 @@
+use crate::item::Item;
+use crate::rucksack::RucksackPair;
+use crate::rucksack_reader::{RucksackReader, RucksackReaderError};
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ReorganizeError {
+    #[error("No arrangement with equal compartments separates every item")]
+    ImpossibleError(),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Compartment {
+    Left,
+    Right,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ItemMove {
+    pub item: Item,
+    pub count: usize,
+    pub from: Compartment,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReorganizationPlan {
+    pub moves: Vec<ItemMove>,
+}
+
+impl ReorganizationPlan {
+    pub fn move_count(&self) -> usize {
+        self.moves.iter().map(|item_move| item_move.count).sum()
+    }
+}
+
+#[derive(Debug)]
+pub struct ReorganizationReport {
+    // One entry per line, in file order.
+    pub plans: Vec<Result<ReorganizationPlan, ReorganizeError>>,
+}
+
+impl ReorganizationReport {
+    // Moves across every line that could be reorganized.
+    pub fn total_moves(&self) -> usize {
+        self.plans
+            .iter()
+            .filter_map(|plan| plan.as_ref().ok())
+            .map(ReorganizationPlan::move_count)
+            .sum()
+    }
+}
+
+// Finds the fewest item moves that leave no item in both compartments, with
+// both compartments still the same size.
+//
+// Every kind of item has to end up entirely on one side, so this chooses a
+// side for each kind. The kinds chosen for the left must add up to exactly
+// half the items, and every item that starts on the other side costs a move.
+// That's a subset-sum problem over item counts, solved with a table of the
+// most items that can stay put for each possible left compartment size.
+pub fn plan_reorganization(pair: &RucksackPair) -> Result<ReorganizationPlan, ReorganizeError> {
+    let (left, right) = pair.get_rucksacks();
+    let half = left.get_items().len();
+
+    let mut counts: BTreeMap<Item, (usize, usize)> = BTreeMap::new();
+    for item in left.get_items() {
+        counts.entry(item.clone()).or_default().0 += 1;
+    }
+    for item in right.get_items() {
+        counts.entry(item.clone()).or_default().1 += 1;
+    }
+
+    let kinds: Vec<(Item, usize, usize)> = counts
+        .into_iter()
+        .map(|(item, (left_count, right_count))| (item, left_count, right_count))
+        .collect();
+
+    // stay[i][s] is the most items that can stay put when the first i kinds
+    // put s items in the left compartment.
+    let mut stay = vec![vec![None; half + 1]; kinds.len() + 1];
+    stay[0][0] = Some(0);
+
+    for (i, &(_, left_count, right_count)) in kinds.iter().enumerate() {
+        for size in 0..=half {
+            let Some(stayed) = stay[i][size] else {
+                continue;
+            };
+
+            let right_choice = stayed + right_count;
+            if stay[i + 1][size].is_none_or(|best| right_choice > best) {
+                stay[i + 1][size] = Some(right_choice);
+            }
+
+            let left_size = size + left_count + right_count;
+            let left_choice = stayed + left_count;
+            if left_size <= half && stay[i + 1][left_size].is_none_or(|best| left_choice > best) {
+                stay[i + 1][left_size] = Some(left_choice);
+            }
+        }
+    }
+
+    let mut stayed = stay[kinds.len()][half].ok_or(ReorganizeError::ImpossibleError())?;
+    let mut size = half;
+    let mut moves = Vec::new();
+
+    for (i, (item, left_count, right_count)) in kinds.iter().enumerate().rev() {
+        let (left_count, right_count) = (*left_count, *right_count);
+        let kind_size = left_count + right_count;
+        let goes_left = size >= kind_size
+            && stayed
+                .checked_sub(left_count)
+                .is_some_and(|rest| stay[i][size - kind_size] == Some(rest));
+
+        let item_move = if goes_left {
+            size -= kind_size;
+            stayed -= left_count;
+
+            ItemMove {
+                item: item.clone(),
+                count: right_count,
+                from: Compartment::Right,
+            }
+        } else {
+            stayed -= right_count;
+
+            ItemMove {
+                item: item.clone(),
+                count: left_count,
+                from: Compartment::Left,
+            }
+        };
+
+        if item_move.count > 0 {
+            moves.push(item_move);
+        }
+    }
+
+    moves.reverse();
+
+    Ok(ReorganizationPlan { moves })
+}
+
+pub fn read_and_plan_reorganization(
+    filename: &str,
+) -> Result<ReorganizationReport, RucksackReaderError> {
+    let rucksack_pairs = RucksackReader::read_from_file(filename)?;
+
+    Ok(ReorganizationReport {
+        plans: rucksack_pairs.iter().map(plan_reorganization).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(pair: &RucksackPair, plan: &ReorganizationPlan) -> (Vec<Item>, Vec<Item>) {
+        let (left, right) = pair.get_rucksacks();
+        let mut left = left.get_items().clone();
+        let mut right = right.get_items().clone();
+
+        for item_move in &plan.moves {
+            let (from, to) = match item_move.from {
+                Compartment::Left => (&mut left, &mut right),
+                Compartment::Right => (&mut right, &mut left),
+            };
+
+            for _ in 0..item_move.count {
+                let position = from
+                    .iter()
+                    .position(|item| *item == item_move.item)
+                    .unwrap();
+                to.push(from.remove(position));
+            }
+        }
+
+        (left, right)
+    }
+
+    fn assert_separated(pair: &RucksackPair, plan: &ReorganizationPlan) {
+        let (left, right) = apply(pair, plan);
+
+        assert_eq!(left.len(), right.len());
+        assert!(left.iter().all(|item| !right.contains(item)));
+    }
+
+    #[test]
+    fn test_plan_reorganization_without_shared_items() {
+        let pair = RucksackPair::try_from(String::from("abcdef")).unwrap();
+
+        assert_eq!(
+            plan_reorganization(&pair).unwrap(),
+            ReorganizationPlan::default()
+        );
+    }
+
+    #[test]
+    fn test_plan_reorganization_single_shared_item() {
+        let pair = RucksackPair::try_from(String::from("abccde")).unwrap();
+
+        let plan = plan_reorganization(&pair).unwrap();
+
+        assert_eq!(plan.move_count(), 2);
+        assert_separated(&pair, &plan);
+    }
+
+    #[test]
+    fn test_plan_reorganization_multiple_shared_items() {
+        let pair = RucksackPair::try_from(String::from("aabbcxabbcyy")).unwrap();
+
+        let plan = plan_reorganization(&pair).unwrap();
+
+        assert_eq!(plan.move_count(), 4);
+        assert_separated(&pair, &plan);
+    }
+
+    #[test]
+    fn test_plan_reorganization_impossible() {
+        let pair = RucksackPair::try_from(String::from("aabaaa")).unwrap();
+
+        assert_eq!(
+            plan_reorganization(&pair).unwrap_err(),
+            ReorganizeError::ImpossibleError()
+        );
+    }
+
+    #[test]
+    fn test_plan_reorganization_more_left_items_than_stayed() {
+        let pair = RucksackPair::try_from(String::from("dbbaccaa")).unwrap();
+
+        let plan = plan_reorganization(&pair).unwrap();
+
+        assert_eq!(plan.move_count(), 4);
+        assert_separated(&pair, &plan);
+    }
+
+    // The fewest moves found by trying every side for every kind of item.
+    fn brute_force_moves(line: &str) -> Option<usize> {
+        let (left, right) = line.split_at(line.len() / 2);
+        let kinds: Vec<char> = {
+            let mut kinds: Vec<char> = line.chars().collect();
+            kinds.sort();
+            kinds.dedup();
+            kinds
+        };
+
+        (0..1usize << kinds.len())
+            .filter_map(|sides| {
+                let goes_left = |c: char| {
+                    let kind = kinds.iter().position(|&kind| kind == c).unwrap();
+                    sides & (1 << kind) != 0
+                };
+
+                let left_size = line.chars().filter(|&c| goes_left(c)).count();
+                let moves = left.chars().filter(|&c| !goes_left(c)).count()
+                    + right.chars().filter(|&c| goes_left(c)).count();
+
+                (left_size == left.len()).then_some(moves)
+            })
+            .min()
+    }
+
+    #[test]
+    fn test_plan_reorganization_matches_brute_force() {
+        let alphabet = ['a', 'b', 'c', 'd'];
+
+        for length in (2..=8u32).step_by(2) {
+            for index in 0..alphabet.len().pow(length) {
+                let line: String = (0..length)
+                    .map(|place| alphabet[index / alphabet.len().pow(place) % alphabet.len()])
+                    .collect();
+                let pair = RucksackPair::try_from(line.clone()).unwrap();
+
+                match (plan_reorganization(&pair), brute_force_moves(&line)) {
+                    (Ok(plan), Some(moves)) => {
+                        assert_eq!(plan.move_count(), moves, "{}", line);
+                        assert_separated(&pair, &plan);
+                    }
+                    (Err(_), None) => {}
+                    (plan, moves) => panic!("{}: {:?} vs {:?}", line, plan, moves),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_read_and_plan_reorganization() {
+        let report = read_and_plan_reorganization("fixtures/rucksacks.txt").unwrap();
+        let pairs = RucksackReader::read_from_file("fixtures/rucksacks.txt").unwrap();
+
+        assert_eq!(report.plans.len(), 6);
+
+        for (pair, plan) in pairs.iter().zip(&report.plans) {
+            assert_separated(pair, plan.as_ref().unwrap());
+        }
+
+        assert_eq!(
+            report.total_moves(),
+            report
+                .plans
+                .iter()
+                .map(|plan| plan.as_ref().unwrap().move_count())
+                .sum::<usize>()
+        );
+    }
+}
@@ -1,17 +1,38 @@
+use crate::item::{parse_items, Item};
+use crate::item_set::ItemSet;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum RucksackPairParserError {
     #[error("Invalid string length: {0}")]
     InvalidLengthError(usize),
+    #[error("Expected exactly one separator {0:?}, found {1}")]
+    InvalidSeparatorCountError(char, usize),
 }
 
+// How a line is divided into its two compartments.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SplitPolicy {
+    // Split down the middle, rejecting lines with an odd number of items.
+    #[default]
+    Reject,
+    // Split down the middle, with the middle item of an odd line going left.
+    MiddleToLeft,
+    // Split down the middle, with the middle item of an odd line going right.
+    MiddleToRight,
+    // Split on the one occurrence of this character, which isn't an item.
+    Separator(char),
+}
+
+#[allow(clippy::enum_variant_names)]
 #[derive(Debug, Error)]
 pub enum RucksackGetCommonItemError {
     #[error("No repeated item found")]
     NoRepeatedItemError(),
     #[error("Multiple repeated items found: {0:?}")]
-    MultipleRepeatedCharError(Vec<char>),
+    MultipleRepeatedCharError(Vec<Item>),
+    #[error("Group has no rucksacks")]
+    EmptyGroupError(),
 }
 
 #[derive(Debug)]
@@ -20,84 +41,119 @@ pub struct RucksackPair {
 }
 
 impl RucksackPair {
-    pub fn get_common_item(&self) -> Result<char, RucksackGetCommonItemError> {
-        let mut repeated_chars = Vec::new();
-
-        for item in self.rucksacks.0.get_items() {
-            if self.rucksacks.1.get_items().contains(item) {
-                if repeated_chars.contains(item) {
-                    continue;
-                }
+    pub fn get_common_item(&self) -> Result<Item, RucksackGetCommonItemError> {
+        let common_items = self
+            .rucksacks
+            .0
+            .get_item_set()
+            .intersection(self.rucksacks.1.get_item_set());
 
-                repeated_chars.push(*item);
-            }
-        }
-
-        match repeated_chars.len() {
-            0 => Err(RucksackGetCommonItemError::NoRepeatedItemError()),
-            1 => Ok(repeated_chars[0]),
-            _ => Err(RucksackGetCommonItemError::MultipleRepeatedCharError(
-                repeated_chars,
-            )),
-        }
+        self.single_common_item(common_items)
     }
 
     pub fn get_common_item_with(
         &self,
-        others: (&RucksackPair, &RucksackPair),
-    ) -> Result<char, RucksackGetCommonItemError> {
-        let my_items = self.get_items();
-        let other_items = (others.0.get_items().to_vec(), others.1.get_items().to_vec());
+        others: &[RucksackPair],
+    ) -> Result<Item, RucksackGetCommonItemError> {
+        let common_items = others
+            .iter()
+            .fold(self.get_item_set(), |common_items, other| {
+                common_items.intersection(&other.get_item_set())
+            });
 
-        let mut repeated_chars = Vec::new();
-
-        for item in my_items {
-            if other_items.0.contains(&item) && other_items.1.contains(&item) {
-                if repeated_chars.contains(&item) {
-                    continue;
-                }
+        self.single_common_item(common_items)
+    }
 
-                repeated_chars.push(item);
-            }
-        }
+    pub fn get_items(&self) -> impl Iterator<Item = &Item> {
+        self.rucksacks
+            .0
+            .get_items()
+            .iter()
+            .chain(self.rucksacks.1.get_items())
+    }
 
-        match repeated_chars.len() {
-            0 => Err(RucksackGetCommonItemError::NoRepeatedItemError()),
-            1 => Ok(repeated_chars[0]),
-            _ => Err(RucksackGetCommonItemError::MultipleRepeatedCharError(
-                repeated_chars,
-            )),
-        }
+    pub fn get_item_set(&self) -> ItemSet {
+        self.rucksacks
+            .0
+            .get_item_set()
+            .union(self.rucksacks.1.get_item_set())
     }
 
-    pub fn get_items(&self) -> Vec<char> {
-        let mut left_items = self.rucksacks.0.get_items().clone();
-        let mut right_items = self.rucksacks.1.get_items().clone();
+    // When there are several common items, they're reported in the order they
+    // first appear in this pair.
+    fn single_common_item(
+        &self,
+        common_items: ItemSet,
+    ) -> Result<Item, RucksackGetCommonItemError> {
+        match common_items.len() {
+            0 => Err(RucksackGetCommonItemError::NoRepeatedItemError()),
+            1 => Ok(common_items.items().remove(0)),
+            _ => {
+                let mut repeated_chars = Vec::new();
 
-        left_items.append(right_items.as_mut());
+                for item in self.get_items() {
+                    if common_items.contains(item) && !repeated_chars.contains(item) {
+                        repeated_chars.push(item.clone());
+                    }
+                }
 
-        left_items
+                Err(RucksackGetCommonItemError::MultipleRepeatedCharError(
+                    repeated_chars,
+                ))
+            }
+        }
     }
 
-    #[cfg(test)]
-    fn get_rucksacks(&self) -> &(Rucksack, Rucksack) {
+    pub fn get_rucksacks(&self) -> &(Rucksack, Rucksack) {
         &self.rucksacks
     }
 }
 
-impl TryFrom<String> for RucksackPair {
-    type Error = RucksackPairParserError;
+// The one item shared by every rucksack pair in the group.
+pub fn get_group_common_item(group: &[RucksackPair]) -> Result<Item, RucksackGetCommonItemError> {
+    match group.split_first() {
+        Some((first, others)) => first.get_common_item_with(others),
+        None => Err(RucksackGetCommonItemError::EmptyGroupError()),
+    }
+}
 
-    fn try_from(s: String) -> Result<Self, Self::Error> {
-        let chars: Vec<char> = s.chars().collect();
-        let char_count = chars.len();
+impl RucksackPair {
+    pub fn parse_with_policy(
+        s: &str,
+        policy: SplitPolicy,
+    ) -> Result<Self, RucksackPairParserError> {
+        let (first_group, second_group) = match policy {
+            // Split before segmenting, so a combining mark right after the
+            // separator can't join it into a single item.
+            SplitPolicy::Separator(separator) => {
+                let separator_count = s.matches(separator).count();
+                if separator_count != 1 {
+                    return Err(RucksackPairParserError::InvalidSeparatorCountError(
+                        separator,
+                        separator_count,
+                    ));
+                }
 
-        if chars.len() % 2 != 0 {
-            return Err(RucksackPairParserError::InvalidLengthError(char_count));
-        }
+                let (first, second) = s.split_once(separator).unwrap();
+                (parse_items(first), parse_items(second))
+            }
+            _ => {
+                let mut items = parse_items(s);
+                let item_count = items.len();
 
-        let first_group = chars[0..char_count / 2].to_vec();
-        let second_group = chars[char_count / 2..char_count].to_vec();
+                let middle = match policy {
+                    _ if item_count.is_multiple_of(2) => item_count / 2,
+                    SplitPolicy::Reject => {
+                        return Err(RucksackPairParserError::InvalidLengthError(item_count))
+                    }
+                    SplitPolicy::MiddleToLeft => item_count / 2 + 1,
+                    _ => item_count / 2,
+                };
+
+                let second = items.split_off(middle);
+                (items, second)
+            }
+        };
 
         Ok(RucksackPair {
             rucksacks: (Rucksack::new(first_group), Rucksack::new(second_group)),
@@ -105,19 +161,38 @@ impl TryFrom<String> for RucksackPair {
     }
 }
 
+impl TryFrom<String> for RucksackPair {
+    type Error = RucksackPairParserError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::parse_with_policy(&s, SplitPolicy::Reject)
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct Rucksack {
-    items: Vec<char>,
+    items: Vec<Item>,
+    item_set: ItemSet,
 }
 
 impl Rucksack {
-    pub fn new(items: Vec<char>) -> Rucksack {
-        Rucksack { items }
+    pub fn new<T>(items: Vec<T>) -> Rucksack
+    where
+        T: Into<Item>,
+    {
+        let items: Vec<Item> = items.into_iter().map(Into::into).collect();
+        let item_set = ItemSet::from_items(&items);
+
+        Rucksack { items, item_set }
     }
 
-    pub fn get_items(&self) -> &Vec<char> {
+    pub fn get_items(&self) -> &Vec<Item> {
         &self.items
     }
+
+    pub fn get_item_set(&self) -> &ItemSet {
+        &self.item_set
+    }
 }
 
 #[cfg(test)]
@@ -140,10 +215,116 @@ pub mod tests {
         RucksackPair::try_from(String::from("abcde")).unwrap();
     }
 
+    #[test]
+    fn test_rucksack_pair_parser_odd_length_policies() {
+        let left = RucksackPair::parse_with_policy("abcde", SplitPolicy::MiddleToLeft).unwrap();
+        let right = RucksackPair::parse_with_policy("abcde", SplitPolicy::MiddleToRight).unwrap();
+
+        assert_eq!(
+            left.get_rucksacks(),
+            &(
+                Rucksack::new(vec!['a', 'b', 'c']),
+                Rucksack::new(vec!['d', 'e'])
+            )
+        );
+        assert_eq!(
+            right.get_rucksacks(),
+            &(
+                Rucksack::new(vec!['a', 'b']),
+                Rucksack::new(vec!['c', 'd', 'e'])
+            )
+        );
+    }
+
+    #[test]
+    fn test_rucksack_pair_parser_separator_policy() {
+        let pair = RucksackPair::parse_with_policy("abc|de", SplitPolicy::Separator('|')).unwrap();
+
+        assert_eq!(
+            pair.get_rucksacks(),
+            &(
+                Rucksack::new(vec!['a', 'b', 'c']),
+                Rucksack::new(vec!['d', 'e'])
+            )
+        );
+        assert!(matches!(
+            RucksackPair::parse_with_policy("abcde", SplitPolicy::Separator('|')),
+            Err(RucksackPairParserError::InvalidSeparatorCountError('|', 0))
+        ));
+    }
+
+    #[test]
+    fn test_rucksack_pair_parser_grapheme_clusters() {
+        let rucksack_pair = RucksackPair::try_from(String::from("ae\u{301}ze\u{301}")).unwrap();
+
+        assert_eq!(
+            rucksack_pair.get_common_item().unwrap(),
+            Item::from("e\u{301}")
+        );
+    }
+
+    #[test]
+    fn test_rucksack_pair_parser_separator_before_combining_mark() {
+        let pair =
+            RucksackPair::parse_with_policy("ab|\u{301}c", SplitPolicy::Separator('|')).unwrap();
+        let (_, second) = pair.get_rucksacks();
+
+        assert_eq!(
+            second.get_items(),
+            &vec![Item::from("\u{301}"), Item::Char('c')]
+        );
+    }
+
+    #[test]
+    fn test_rucksack_pair_common_item_across_normal_forms() {
+        let rucksack_pair = RucksackPair::try_from(String::from("a\u{e9}ze\u{301}")).unwrap();
+
+        assert_eq!(rucksack_pair.get_common_item().unwrap(), '\u{e9}');
+    }
+
     #[test]
     fn test_rucksack_pair_get_repeated_char() {
         let rucksack_pair = RucksackPair::try_from(String::from("abccde")).unwrap();
 
         assert_eq!(rucksack_pair.get_common_item().unwrap(), 'c');
     }
+
+    #[test]
+    fn test_rucksack_pair_get_repeated_char_multiple() {
+        let rucksack_pair = RucksackPair::try_from(String::from("cabbac")).unwrap();
+
+        assert!(matches!(
+            rucksack_pair.get_common_item(),
+            Err(RucksackGetCommonItemError::MultipleRepeatedCharError(chars)) if chars == vec!['c', 'a', 'b']
+        ));
+    }
+
+    #[test]
+    fn test_rucksack_pair_get_common_item_with() {
+        let first = RucksackPair::try_from(String::from("abcd")).unwrap();
+        let others = vec![
+            RucksackPair::try_from(String::from("xbyz")).unwrap(),
+            RucksackPair::try_from(String::from("bqrs")).unwrap(),
+        ];
+
+        assert_eq!(first.get_common_item_with(&others).unwrap(), 'b');
+    }
+
+    #[test]
+    fn test_get_group_common_item() {
+        let group: Vec<RucksackPair> = ["abcd", "xbyz", "bqrs", "stub"]
+            .iter()
+            .map(|line| RucksackPair::try_from(line.to_string()).unwrap())
+            .collect();
+
+        assert_eq!(get_group_common_item(&group).unwrap(), 'b');
+        assert_eq!(
+            get_group_common_item(&group[..1]).unwrap_err().to_string(),
+            "Multiple repeated items found: ['a', 'b', 'c', 'd']"
+        );
+        assert!(matches!(
+            get_group_common_item(&[]),
+            Err(RucksackGetCommonItemError::EmptyGroupError())
+        ));
+    }
 }
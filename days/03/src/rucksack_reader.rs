@@ -1,4 +1,4 @@
-use crate::rucksack::{RucksackPair, RucksackPairParserError};
+use crate::rucksack::{RucksackPair, RucksackPairParserError, SplitPolicy};
 use std::fs::File;
 use std::io::{self, BufRead};
 use std::path::Path;
@@ -12,22 +12,70 @@ pub enum RucksackReaderError {
     RucksackPairParserError(#[from] RucksackPairParserError),
 }
 
+#[derive(Debug)]
+pub struct RejectedLine {
+    pub line_number: usize,
+    pub line: String,
+    pub error: RucksackPairParserError,
+}
+
+#[derive(Debug)]
+pub struct RucksackReadReport {
+    pub rucksack_pairs: Vec<RucksackPair>,
+    pub rejected_lines: Vec<RejectedLine>,
+}
+
 pub struct RucksackReader {}
 
 impl RucksackReader {
     pub fn read_from_file(filename: &str) -> Result<Vec<RucksackPair>, RucksackReaderError> {
+        Self::read_from_file_with_policy(filename, SplitPolicy::Reject)
+    }
+
+    pub fn read_from_file_with_policy(
+        filename: &str,
+        policy: SplitPolicy,
+    ) -> Result<Vec<RucksackPair>, RucksackReaderError> {
         let mut rucksack_pairs = Vec::new();
 
         for line in read_lines(filename)? {
             let line = line?;
-            let pair = RucksackPair::try_from(line)?;
+            let pair = RucksackPair::parse_with_policy(&line, policy)?;
 
             rucksack_pairs.push(pair);
         }
 
         Ok(rucksack_pairs)
     }
+
+    // Reads every line it can, recording the ones that can't be split instead
+    // of stopping at the first. Line numbers start at 1.
+    pub fn read_from_file_lenient(
+        filename: &str,
+        policy: SplitPolicy,
+    ) -> Result<RucksackReadReport, RucksackReaderError> {
+        let mut report = RucksackReadReport {
+            rucksack_pairs: Vec::new(),
+            rejected_lines: Vec::new(),
+        };
+
+        for (i, line) in read_lines(filename)?.enumerate() {
+            let line = line?;
+
+            match RucksackPair::parse_with_policy(&line, policy) {
+                Ok(pair) => report.rucksack_pairs.push(pair),
+                Err(error) => report.rejected_lines.push(RejectedLine {
+                    line_number: i + 1,
+                    line,
+                    error,
+                }),
+            }
+        }
+
+        Ok(report)
+    }
 }
+
 fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
 where
     P: AsRef<Path>,
@@ -35,3 +83,40 @@ where
     let file = File::open(filename)?;
     Ok(io::BufReader::new(file).lines())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_from_file_lenient() {
+        let report = RucksackReader::read_from_file_lenient(
+            "fixtures/rucksacks_odd_lengths.txt",
+            SplitPolicy::Reject,
+        )
+        .unwrap();
+
+        assert_eq!(report.rucksack_pairs.len(), 2);
+        assert_eq!(
+            report
+                .rejected_lines
+                .iter()
+                .map(|rejected| rejected.line_number)
+                .collect::<Vec<_>>(),
+            vec![2, 4]
+        );
+        assert_eq!(report.rejected_lines[0].line, "abcab");
+    }
+
+    #[test]
+    fn test_read_from_file_with_policy() {
+        let pairs = RucksackReader::read_from_file_with_policy(
+            "fixtures/rucksacks_odd_lengths.txt",
+            SplitPolicy::MiddleToRight,
+        )
+        .unwrap();
+
+        assert_eq!(pairs.len(), 4);
+        assert!(RucksackReader::read_from_file("fixtures/rucksacks_odd_lengths.txt").is_err());
+    }
+}
@@ -0,0 +1,207 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+use std::ops::RangeInclusive;
+
+// A graph with a node for each range and an edge between every two ranges
+// that share a section. Nodes are numbered in the order the ranges were given.
+//
+// Because it's an interval graph, the usual hard problems are easy here: a
+// sweep in order of start finds the largest clique, and greedily colouring in
+// that order uses exactly as many colours as the largest clique has nodes.
+#[derive(Clone, Debug)]
+pub struct ConflictGraph<T> {
+    ranges: Vec<RangeInclusive<T>>,
+    neighbours: Vec<Vec<usize>>,
+}
+
+impl<T: Copy + Ord> ConflictGraph<T> {
+    // Empty ranges become nodes with no edges.
+    pub fn new(ranges: Vec<RangeInclusive<T>>) -> Self {
+        let mut neighbours = vec![Vec::new(); ranges.len()];
+        let mut active: Vec<usize> = Vec::new();
+
+        for node in sweep_order(&ranges) {
+            let start = *ranges[node].start();
+            active.retain(|&other| *ranges[other].end() >= start);
+
+            for &other in &active {
+                neighbours[node].push(other);
+                neighbours[other].push(node);
+            }
+
+            active.push(node);
+        }
+
+        for node_neighbours in &mut neighbours {
+            node_neighbours.sort_unstable();
+        }
+
+        ConflictGraph { ranges, neighbours }
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.neighbours.iter().map(Vec::len).sum::<usize>() / 2
+    }
+
+    pub fn range(&self, node: usize) -> &RangeInclusive<T> {
+        &self.ranges[node]
+    }
+
+    pub fn neighbours(&self, node: usize) -> &[usize] {
+        &self.neighbours[node]
+    }
+
+    // Groups of nodes linked by chains of overlaps, each sorted, and ordered
+    // by their smallest node.
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        let mut seen = vec![false; self.node_count()];
+        let mut components = Vec::new();
+
+        for root in 0..self.node_count() {
+            if seen[root] {
+                continue;
+            }
+
+            seen[root] = true;
+            let mut component = vec![root];
+            let mut queue = VecDeque::from([root]);
+
+            while let Some(node) = queue.pop_front() {
+                for &neighbour in &self.neighbours[node] {
+                    if !seen[neighbour] {
+                        seen[neighbour] = true;
+                        component.push(neighbour);
+                        queue.push_back(neighbour);
+                    }
+                }
+            }
+
+            component.sort_unstable();
+            components.push(component);
+        }
+
+        components
+    }
+
+    // The most ranges that all share one section, sorted. Empty only when
+    // every range is.
+    pub fn largest_clique(&self) -> Vec<usize> {
+        let mut active: BinaryHeap<Reverse<(T, usize)>> = BinaryHeap::new();
+        let mut largest = Vec::new();
+
+        for node in sweep_order(&self.ranges) {
+            let start = *self.ranges[node].start();
+            while active.peek().is_some_and(|Reverse((end, _))| *end < start) {
+                active.pop();
+            }
+
+            active.push(Reverse((*self.ranges[node].end(), node)));
+
+            if active.len() > largest.len() {
+                largest = active.iter().map(|Reverse((_, node))| *node).collect();
+            }
+        }
+
+        largest.sort_unstable();
+        largest
+    }
+
+    // A shift for every node such that no two overlapping ranges share one,
+    // using as few shifts as possible. Shifts are numbered from 0, and empty
+    // ranges are all put in shift 0.
+    pub fn colouring(&self) -> Vec<usize> {
+        let mut shifts = vec![0; self.node_count()];
+        let mut active: BinaryHeap<Reverse<(T, usize)>> = BinaryHeap::new();
+        let mut free: BinaryHeap<Reverse<usize>> = BinaryHeap::new();
+        let mut shift_count = 0;
+
+        for node in sweep_order(&self.ranges) {
+            let start = *self.ranges[node].start();
+            while let Some(Reverse((end, finished))) = active.peek().copied() {
+                if end >= start {
+                    break;
+                }
+
+                active.pop();
+                free.push(Reverse(shifts[finished]));
+            }
+
+            shifts[node] = match free.pop() {
+                Some(Reverse(shift)) => shift,
+                None => {
+                    shift_count += 1;
+                    shift_count - 1
+                }
+            };
+
+            active.push(Reverse((*self.ranges[node].end(), node)));
+        }
+
+        shifts
+    }
+
+    // How many shifts `colouring` needs.
+    pub fn shift_count(&self) -> usize {
+        self.largest_clique().len()
+    }
+}
+
+// Non-empty nodes, in order of start.
+fn sweep_order<T: Copy + Ord>(ranges: &[RangeInclusive<T>]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..ranges.len())
+        .filter(|&node| !ranges[node].is_empty())
+        .collect();
+    order.sort_by_key(|&node| (*ranges[node].start(), *ranges[node].end()));
+
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph() -> ConflictGraph<u32> {
+        ConflictGraph::new(vec![1..=4, 10..=12, 3..=6, 5..=8, 12..=12, 4..=5, 20..=25])
+    }
+
+    #[test]
+    fn test_edges() {
+        let graph = graph();
+
+        assert_eq!(graph.edge_count(), 6);
+        assert_eq!(graph.neighbours(2), &[0, 3, 5]);
+        assert_eq!(graph.neighbours(6), &[] as &[usize]);
+    }
+
+    #[test]
+    fn test_components() {
+        assert_eq!(
+            graph().components(),
+            vec![vec![0, 2, 3, 5], vec![1, 4], vec![6]]
+        );
+    }
+
+    #[test]
+    fn test_largest_clique() {
+        assert_eq!(graph().largest_clique(), vec![0, 2, 5]);
+    }
+
+    #[test]
+    fn test_colouring() {
+        let graph = graph();
+        let shifts = graph.colouring();
+
+        assert_eq!(graph.shift_count(), 3);
+        assert!(shifts.iter().all(|&shift| shift < 3));
+
+        for node in 0..graph.node_count() {
+            for &neighbour in graph.neighbours(node) {
+                assert_ne!(shifts[node], shifts[neighbour]);
+            }
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use crate::interval_set::{IntervalSet, Section};
+use std::ops::RangeInclusive;
+
+// How many assignments cover each section, stored as runs of sections that
+// share the same count so it stays small however wide the ranges are.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CoverageReport<T: Section> {
+    // Sorted, non-overlapping runs with a non-zero count. Sections outside
+    // every run aren't covered at all.
+    pub segments: Vec<(RangeInclusive<T>, usize)>,
+}
+
+impl<T: Section> CoverageReport<T> {
+    // Builds the report with a sweep line: each range adds one at its start
+    // and removes one just after its end, and the running total between
+    // consecutive events is the coverage of that stretch. This is
+    // O(n log n) in the number of ranges, independent of their widths.
+    pub fn from_ranges<I>(ranges: I) -> Self
+    where
+        I: IntoIterator<Item = RangeInclusive<T>>,
+    {
+        let mut events: Vec<(T, i64)> = Vec::new();
+        let mut last_end: Option<T> = None;
+
+        for range in ranges {
+            let (start, end) = (*range.start(), *range.end());
+            if start > end {
+                continue;
+            }
+
+            events.push((start, 1));
+            if let Some(after_end) = end.next() {
+                events.push((after_end, -1));
+            }
+
+            last_end = last_end.max(Some(end));
+        }
+
+        events.sort();
+
+        let mut segments = Vec::new();
+        let mut count: i64 = 0;
+        let mut i = 0;
+
+        while i < events.len() {
+            let position = events[i].0;
+
+            while i < events.len() && events[i].0 == position {
+                count += events[i].1;
+                i += 1;
+            }
+
+            if count == 0 {
+                continue;
+            }
+
+            // Runs to just before the next event, or to the last end when
+            // only ranges ending at the type's maximum remain.
+            let end = match events.get(i) {
+                Some(&(next_position, _)) => next_position.prev().unwrap(),
+                None => last_end.unwrap(),
+            };
+
+            segments.push((position..=end, count as usize));
+        }
+
+        CoverageReport { segments }
+    }
+
+    pub fn coverage_at(&self, section: T) -> usize {
+        let index = self
+            .segments
+            .partition_point(|(range, _)| *range.end() < section);
+
+        match self.segments.get(index) {
+            Some((range, count)) if range.contains(&section) => *count,
+            _ => 0,
+        }
+    }
+
+    // The most assignments covering any one section.
+    pub fn max_overlap(&self) -> usize {
+        self.segments
+            .iter()
+            .map(|(_, count)| *count)
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn max_overlap_sections(&self) -> IntervalSet<T> {
+        let max_overlap = self.max_overlap();
+
+        self.sections_where(|count| count == max_overlap)
+    }
+
+    pub fn covered_more_than(&self, times: usize) -> IntervalSet<T> {
+        self.sections_where(|count| count > times)
+    }
+
+    fn sections_where<F>(&self, predicate: F) -> IntervalSet<T>
+    where
+        F: Fn(usize) -> bool,
+    {
+        self.segments
+            .iter()
+            .filter(|(_, count)| predicate(*count))
+            .map(|(range, _)| range.clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_ranges() {
+        let report = CoverageReport::from_ranges([2..=4, 6..=8, 3..=6, 10..=10]);
+
+        assert_eq!(
+            report.segments,
+            vec![
+                (2..=2, 1),
+                (3..=4, 2),
+                (5..=5, 1),
+                (6..=6, 2),
+                (7..=8, 1),
+                (10..=10, 1)
+            ]
+        );
+        assert_eq!(report.coverage_at(3), 2);
+        assert_eq!(report.coverage_at(9), 0);
+        assert_eq!(report.coverage_at(11), 0);
+    }
+
+    #[test]
+    fn test_max_overlap() {
+        let report = CoverageReport::from_ranges([1..=10, 2..=5, 4..=8, 9..=9]);
+
+        assert_eq!(report.max_overlap(), 3);
+        assert_eq!(report.max_overlap_sections().ranges(), vec![4..=5]);
+        assert_eq!(report.covered_more_than(1).ranges(), vec![2..=9]);
+    }
+
+    #[test]
+    fn test_ranges_ending_at_type_maximum() {
+        let report = CoverageReport::from_ranges([250..=255u8, 254..=255]);
+
+        assert_eq!(report.segments, vec![(250..=253, 1), (254..=255, 2)]);
+    }
+
+    #[test]
+    fn test_empty() {
+        let report = CoverageReport::<u32>::from_ranges([]);
+
+        assert_eq!(report.max_overlap(), 0);
+        assert!(report.covered_more_than(0).is_empty());
+    }
+}
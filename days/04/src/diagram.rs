@@ -0,0 +1,437 @@
+use crate::interval_set::Section;
+use std::collections::BTreeSet;
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+// Section diagrams in the style of the puzzle:
+//
+//   .234.....  2-4
+//   .....678.  6-8
+//
+// Each column is a section, drawn as the last digit of its number when the
+// row's range covers it and as `.` otherwise. Rows end with their range as a
+// label.
+//
+// An optional ruler above the rows spells out each column's section number
+// vertically, most significant digit first, with a minus sign above the
+// digits of a negative section. Long runs of columns that every row treats
+// alike can be compressed to their first and last sections with a `~` column
+// between them; compressed diagrams always carry a ruler, since columns no
+// longer count up one at a time.
+//
+// When parsing, labels are optional but checked if present. Leading lines of
+// only digits and signs are read as the ruler, so a fully covered first row
+// needs its label. Rows only draw the part of their range inside the bound,
+// and their labels match. Empty ranges, and ranges outside the bound, draw as
+// unlabelled rows of dots and can't be read back.
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DiagramOptions<T> {
+    // The sections to draw. Defaults to section 1 up to the last end, like
+    // the puzzle's diagrams.
+    pub bound: Option<RangeInclusive<T>>,
+    pub ruler: bool,
+    // Compress runs of more than this many identical columns.
+    pub compress_runs_longer_than: Option<usize>,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum DiagramError {
+    #[error("Unexpected character {1:?} on line {0}")]
+    UnexpectedCharError(usize, char),
+    #[error("Row on line {0} doesn't line up with the ones above it")]
+    MisalignedRowError(usize),
+    #[error("Row on line {0} covers no sections")]
+    EmptyRowError(usize),
+    #[error("Row on line {0} has a gap in it")]
+    NonContiguousRowError(usize),
+    #[error("Row on line {0} has a digit that doesn't match its section")]
+    DigitMismatchError(usize),
+    #[error("Row on line {0} starts or ends inside a compressed run")]
+    CompressedEndError(usize),
+    #[error("Row on line {0} doesn't match its label")]
+    LabelMismatchError(usize),
+    #[error("Ruler starting on line {0} has a column that isn't a section number")]
+    RulerError(usize),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Column<T> {
+    Section(T),
+    Compressed,
+}
+
+pub fn render_diagram<T>(ranges: &[RangeInclusive<T>], options: &DiagramOptions<T>) -> String
+where
+    T: Section + Display + From<u8>,
+{
+    let ends = ranges.iter().filter(|range| !range.is_empty());
+    let bound = options.bound.clone().unwrap_or_else(|| {
+        let first = ends.clone().map(|range| *range.start()).min();
+        let last = ends.clone().map(|range| *range.end()).max();
+
+        first.map_or(T::from(1), |first| first.min(T::from(1)))..=last.unwrap_or(T::from(1))
+    });
+
+    let columns = columns(ranges, &bound, options.compress_runs_longer_than);
+    let mut lines = Vec::new();
+
+    // Without a ruler the parser assumes the first column is section 1.
+    let ruler = options.ruler
+        || columns.contains(&Column::Compressed)
+        || columns.first() != Some(&Column::Section(T::from(1)));
+    if ruler {
+        lines.extend(render_ruler(&columns));
+    }
+
+    for range in ranges {
+        let row: String = columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| match column {
+                Column::Section(section) if range.contains(section) => last_digit(*section),
+                // Every row treats a compressed run alike, so it's covered
+                // when the run's first section is.
+                Column::Compressed => match columns[i - 1] {
+                    Column::Section(first) if range.contains(&first) => '~',
+                    _ => '.',
+                },
+                _ => '.',
+            })
+            .collect();
+
+        let start = (*range.start()).max(*bound.start());
+        let end = (*range.end()).min(*bound.end());
+        if start <= end {
+            lines.push(format!("{}  {}-{}", row, start, end));
+        } else {
+            lines.push(row);
+        }
+    }
+
+    lines.join("\n") + "\n"
+}
+
+pub fn parse_diagram<T>(text: &str) -> Result<Vec<RangeInclusive<T>>, DiagramError>
+where
+    T: Section + Display + FromStr + From<u8>,
+{
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim_end()))
+        .filter(|(_, line)| !line.is_empty())
+        .peekable();
+
+    let ruler_number = lines.peek().map(|&(number, _)| number);
+    let mut ruler = Vec::new();
+    while let Some((_, line)) = lines.next_if(|(_, line)| is_ruler_line(line)) {
+        ruler.push(line);
+    }
+
+    let mut columns = match ruler_number {
+        Some(number) if !ruler.is_empty() => Some(parse_ruler(number, &ruler)?),
+        _ => None,
+    };
+    let mut ranges = Vec::new();
+
+    for (number, line) in lines {
+        let (cells, label) = match line.split_once(char::is_whitespace) {
+            Some((cells, label)) => (cells, Some(label.trim())),
+            None => (line, None),
+        };
+
+        let columns = columns.get_or_insert_with(|| {
+            std::iter::successors(Some(T::from(1)), |section| section.next())
+                .take(cells.chars().count())
+                .map(Column::Section)
+                .collect()
+        });
+
+        let range = parse_row(number, cells, columns)?;
+
+        if let Some(label) = label {
+            if label != format!("{}-{}", range.start(), range.end()) {
+                return Err(DiagramError::LabelMismatchError(number));
+            }
+        }
+
+        ranges.push(range);
+    }
+
+    Ok(ranges)
+}
+
+// The columns to draw for the bound, compressing long runs of sections over
+// which no range starts or ends.
+fn columns<T: Section>(
+    ranges: &[RangeInclusive<T>],
+    bound: &RangeInclusive<T>,
+    compress_runs_longer_than: Option<usize>,
+) -> Vec<Column<T>> {
+    let sections = |first: T, last: T| {
+        std::iter::successors(Some(first), move |&section| {
+            section.next().filter(|&next| next <= last)
+        })
+    };
+
+    let Some(limit) = compress_runs_longer_than else {
+        return sections(*bound.start(), *bound.end())
+            .map(Column::Section)
+            .collect();
+    };
+
+    // The first section of each run, where some row can change from covered
+    // to uncovered or back.
+    let mut breaks: BTreeSet<T> = BTreeSet::from([*bound.start()]);
+    for range in ranges.iter().filter(|range| !range.is_empty()) {
+        breaks.insert(*range.start());
+        breaks.extend(range.end().next());
+    }
+    let breaks: Vec<T> = breaks
+        .into_iter()
+        .filter(|section| bound.contains(section))
+        .collect();
+
+    let mut columns = Vec::new();
+    for (i, &first) in breaks.iter().enumerate() {
+        let last = breaks
+            .get(i + 1)
+            .and_then(|next| next.prev())
+            .unwrap_or(*bound.end());
+
+        if T::count(first, last) > limit.max(3) as u64 {
+            columns.extend([
+                Column::Section(first),
+                Column::Compressed,
+                Column::Section(last),
+            ]);
+        } else {
+            columns.extend(sections(first, last).map(Column::Section));
+        }
+    }
+
+    columns
+}
+
+fn last_digit<T: Display>(section: T) -> char {
+    section.to_string().chars().last().unwrap()
+}
+
+fn render_ruler<T: Display>(columns: &[Column<T>]) -> Vec<String> {
+    let width = columns
+        .iter()
+        .filter_map(|column| match column {
+            Column::Section(section) => Some(section.to_string().len()),
+            Column::Compressed => None,
+        })
+        .max()
+        .unwrap_or(1);
+
+    (0..width)
+        .map(|place| {
+            columns
+                .iter()
+                .map(|column| match column {
+                    Column::Section(section) => {
+                        let digits = format!("{:>width$}", section, width = width);
+                        digits.chars().nth(place).unwrap()
+                    }
+                    Column::Compressed => '~',
+                })
+                .collect()
+        })
+        .collect()
+}
+
+// Columns run in increasing order, so within each ruler line a minus sign
+// never follows a space. Row labels always have one after theirs.
+fn is_ruler_line(line: &str) -> bool {
+    line.chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, ' ' | '~' | '-'))
+        && line
+            .find(' ')
+            .is_none_or(|space| !line[space..].contains('-'))
+}
+
+fn parse_ruler<T: FromStr>(number: usize, ruler: &[&str]) -> Result<Vec<Column<T>>, DiagramError> {
+    let ruler: Vec<Vec<char>> = ruler.iter().map(|line| line.chars().collect()).collect();
+    let units = &ruler[ruler.len() - 1];
+
+    (0..units.len())
+        .map(|i| {
+            if units[i] == '~' {
+                return Ok(Column::Compressed);
+            }
+
+            let digits: String = ruler
+                .iter()
+                .filter_map(|line| line.get(i))
+                .filter(|c| c.is_ascii_digit() || **c == '-')
+                .collect();
+
+            digits
+                .parse()
+                .map(Column::Section)
+                .map_err(|_| DiagramError::RulerError(number))
+        })
+        .collect()
+}
+
+fn parse_row<T: Section + Display>(
+    number: usize,
+    cells: &str,
+    columns: &[Column<T>],
+) -> Result<RangeInclusive<T>, DiagramError> {
+    let cells: Vec<char> = cells.chars().collect();
+
+    if cells.len() != columns.len() {
+        return Err(DiagramError::MisalignedRowError(number));
+    }
+
+    let mut covered = Vec::new();
+    for (i, (&cell, column)) in cells.iter().zip(columns).enumerate() {
+        match (cell, column) {
+            ('.', _) => {}
+            ('~', Column::Compressed) => covered.push(i),
+            (digit, Column::Section(section)) if digit.is_ascii_digit() => {
+                if digit != last_digit(*section) {
+                    return Err(DiagramError::DigitMismatchError(number));
+                }
+
+                covered.push(i);
+            }
+            (other, _) => return Err(DiagramError::UnexpectedCharError(number, other)),
+        }
+    }
+
+    let (Some(&first), Some(&last)) = (covered.first(), covered.last()) else {
+        return Err(DiagramError::EmptyRowError(number));
+    };
+
+    if last - first + 1 != covered.len() {
+        return Err(DiagramError::NonContiguousRowError(number));
+    }
+
+    match (columns[first], columns[last]) {
+        (Column::Section(start), Column::Section(end)) => Ok(start..=end),
+        _ => Err(DiagramError::CompressedEndError(number)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_puzzle_diagram() {
+        let options = DiagramOptions {
+            bound: Some(1..=9),
+            ..DiagramOptions::default()
+        };
+
+        assert_eq!(
+            render_diagram(&[2..=4, 6..=8], &options),
+            ".234.....  2-4\n.....678.  6-8\n"
+        );
+    }
+
+    #[test]
+    fn test_render_ruler() {
+        let options = DiagramOptions {
+            ruler: true,
+            ..DiagramOptions::default()
+        };
+
+        assert_eq!(
+            render_diagram(&[9..=11], &options),
+            "         11\n12345678901\n........901  9-11\n"
+        );
+    }
+
+    #[test]
+    fn test_render_clips_to_bound() {
+        let options = DiagramOptions {
+            bound: Some(3..=6),
+            ruler: true,
+            ..DiagramOptions::default()
+        };
+
+        let diagram = render_diagram(&[1..=4, 5..=9, 8..=9], &options);
+        assert_eq!(diagram, "3456\n34..  3-4\n..56  5-6\n....\n");
+        assert_eq!(
+            parse_diagram::<u32>(diagram.trim_end_matches("....\n")).unwrap(),
+            vec![3..=4, 5..=6]
+        );
+    }
+
+    #[test]
+    fn test_render_compressed() {
+        let options = DiagramOptions {
+            compress_runs_longer_than: Some(3),
+            ..DiagramOptions::default()
+        };
+
+        assert_eq!(
+            render_diagram(&[2..=50, 50..=52], &options),
+            concat!(
+                "  ~4555\n",
+                "12~9012\n",
+                ".2~90..  2-50\n",
+                "....012  50-52\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_diagram() {
+        assert_eq!(
+            parse_diagram(".234.....\n.....678.  6-8\n").unwrap(),
+            vec![2..=4, 6..=8]
+        );
+        assert_eq!(
+            parse_diagram::<u32>(".23.5....\n"),
+            Err(DiagramError::NonContiguousRowError(1))
+        );
+        assert_eq!(
+            parse_diagram::<u32>(".234.....\n.345.....\n"),
+            Err(DiagramError::DigitMismatchError(2))
+        );
+        assert_eq!(
+            parse_diagram::<u32>(".234.....\n.234...\n"),
+            Err(DiagramError::MisalignedRowError(2))
+        );
+        assert_eq!(
+            parse_diagram::<u32>(".234.....  2-5\n"),
+            Err(DiagramError::LabelMismatchError(1))
+        );
+        assert_eq!(
+            parse_diagram::<u32>("1 3\n.2.  2-2\n"),
+            Err(DiagramError::RulerError(1))
+        );
+        assert_eq!(
+            parse_diagram::<i32>("\n-1\n-1\n.1  11-11\n"),
+            Err(DiagramError::RulerError(2))
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let ranges = vec![0..=3, 7..=1000, 999..=1004, 1004..=1004];
+
+        for options in [
+            DiagramOptions::default(),
+            DiagramOptions {
+                compress_runs_longer_than: Some(5),
+                ..DiagramOptions::default()
+            },
+        ] {
+            let diagram = render_diagram(&ranges, &options);
+
+            assert_eq!(parse_diagram(&diagram).unwrap(), ranges);
+        }
+    }
+}
@@ -0,0 +1,339 @@
+use std::ops::RangeInclusive;
+
+// Section coordinates: discrete, ordered values where every value has a well
+// defined neighbour, so adjacent intervals like 1-3 and 4-6 can be merged.
+pub trait Section: Copy + Ord {
+    fn next(self) -> Option<Self>;
+    fn prev(self) -> Option<Self>;
+    // How many sections start..=end covers, assuming start <= end.
+    fn count(start: Self, end: Self) -> u64;
+}
+
+macro_rules! impl_section {
+    ($($t:ty),*) => {
+        $(
+            impl Section for $t {
+                fn next(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn prev(self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+
+                fn count(start: Self, end: Self) -> u64 {
+                    (end as i128 - start as i128 + 1) as u64
+                }
+            }
+        )*
+    };
+}
+
+impl_section!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+// A non-empty, inclusive interval of sections.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval<T: Section> {
+    start: T,
+    end: T,
+}
+
+impl<T: Section> Interval<T> {
+    // Returns None when start > end, since that interval would be empty.
+    pub fn new(start: T, end: T) -> Option<Self> {
+        if start > end {
+            return None;
+        }
+
+        Some(Interval { start, end })
+    }
+
+    pub fn start(&self) -> T {
+        self.start
+    }
+
+    pub fn end(&self) -> T {
+        self.end
+    }
+
+    pub fn section_count(&self) -> u64 {
+        T::count(self.start, self.end)
+    }
+
+    pub fn contains(&self, section: T) -> bool {
+        self.start <= section && section <= self.end
+    }
+
+    pub fn intersects(&self, other: &Interval<T>) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    // True when the two intervals overlap or sit right next to each other.
+    fn touches(&self, other: &Interval<T>) -> bool {
+        let (first, second) = if self.start <= other.start {
+            (self, other)
+        } else {
+            (other, self)
+        };
+
+        match first.end.next() {
+            Some(after_first) => second.start <= after_first,
+            None => true,
+        }
+    }
+}
+
+impl<T: Section> TryFrom<RangeInclusive<T>> for Interval<T> {
+    type Error = RangeInclusive<T>;
+
+    fn try_from(range: RangeInclusive<T>) -> Result<Self, Self::Error> {
+        Interval::new(*range.start(), *range.end()).ok_or(range)
+    }
+}
+
+impl<T: Section> From<Interval<T>> for RangeInclusive<T> {
+    fn from(interval: Interval<T>) -> Self {
+        interval.start..=interval.end
+    }
+}
+
+// A set of sections, stored as sorted intervals with no overlapping or
+// adjacent neighbours.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IntervalSet<T: Section> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: Section> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet {
+            intervals: Vec::new(),
+        }
+    }
+
+    // Empty ranges are skipped.
+    pub fn from_ranges<I>(ranges: I) -> Self
+    where
+        I: IntoIterator<Item = RangeInclusive<T>>,
+    {
+        let intervals = ranges
+            .into_iter()
+            .filter_map(|range| Interval::try_from(range).ok())
+            .collect();
+
+        Self::from_intervals(intervals)
+    }
+
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        if let Ok(interval) = Interval::try_from(range) {
+            let mut intervals = std::mem::take(&mut self.intervals);
+            intervals.push(interval);
+
+            *self = Self::from_intervals(intervals);
+        }
+    }
+
+    pub fn contains(&self, section: T) -> bool {
+        let index = self
+            .intervals
+            .partition_point(|interval| interval.end < section);
+
+        self.intervals
+            .get(index)
+            .is_some_and(|interval| interval.contains(section))
+    }
+
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        Self::from_intervals(
+            self.intervals
+                .iter()
+                .chain(&other.intervals)
+                .copied()
+                .collect(),
+        )
+    }
+
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut intervals = Vec::new();
+        let (mut i, mut j) = (0, 0);
+
+        while i < self.intervals.len() && j < other.intervals.len() {
+            let (left, right) = (self.intervals[i], other.intervals[j]);
+
+            if let Some(overlap) =
+                Interval::new(left.start.max(right.start), left.end.min(right.end))
+            {
+                intervals.push(overlap);
+            }
+
+            if left.end < right.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        IntervalSet { intervals }
+    }
+
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut intervals = Vec::new();
+        let mut j = 0;
+
+        for interval in &self.intervals {
+            let mut start = Some(interval.start);
+
+            while j < other.intervals.len() && other.intervals[j].end < interval.start {
+                j += 1;
+            }
+
+            let mut k = j;
+            while let Some(current) = start {
+                let removed = match other.intervals.get(k) {
+                    Some(removed) if removed.start <= interval.end => removed,
+                    _ => {
+                        intervals.push(Interval::new(current, interval.end).unwrap());
+                        break;
+                    }
+                };
+
+                if let Some(before) = removed.start.prev() {
+                    if let Some(kept) = Interval::new(current, before) {
+                        intervals.push(kept);
+                    }
+                }
+
+                start = removed.end.next().filter(|&next| next <= interval.end);
+                k += 1;
+            }
+        }
+
+        IntervalSet { intervals }
+    }
+
+    // The sections within the bound that aren't in this set.
+    pub fn gaps(&self, bound: RangeInclusive<T>) -> IntervalSet<T> {
+        IntervalSet::from_ranges([bound]).difference(self)
+    }
+
+    // The total number of sections covered.
+    pub fn len(&self) -> u64 {
+        self.intervals.iter().map(Interval::section_count).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Interval<T>> {
+        self.intervals.iter()
+    }
+
+    pub fn ranges(&self) -> Vec<RangeInclusive<T>> {
+        self.intervals
+            .iter()
+            .map(|&interval| interval.into())
+            .collect()
+    }
+
+    fn from_intervals(mut intervals: Vec<Interval<T>>) -> Self {
+        intervals.sort();
+
+        let mut merged: Vec<Interval<T>> = Vec::with_capacity(intervals.len());
+
+        for interval in intervals {
+            match merged.last_mut() {
+                Some(last) if last.touches(&interval) => last.end = last.end.max(interval.end),
+                _ => merged.push(interval),
+            }
+        }
+
+        IntervalSet { intervals: merged }
+    }
+}
+
+impl<'a, T: Section> IntoIterator for &'a IntervalSet<T> {
+    type Item = &'a Interval<T>;
+    type IntoIter = std::slice::Iter<'a, Interval<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: Section> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(ranges: I) -> Self {
+        Self::from_ranges(ranges)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merges_overlapping_and_adjacent_ranges() {
+        let set = IntervalSet::from_ranges([
+            5..=7,
+            1..=2,
+            3..=4,
+            6..=9,
+            12..=12,
+            RangeInclusive::new(5, 3),
+        ]);
+
+        assert_eq!(set.ranges(), vec![1..=9, 12..=12]);
+        assert_eq!(set.len(), 10);
+    }
+
+    #[test]
+    fn test_merges_at_type_bounds() {
+        let set = IntervalSet::from_ranges([0..=10u8, 250..=255, 11..=20]);
+
+        assert_eq!(set.ranges(), vec![0..=20, 250..=255]);
+        assert_eq!(set.gaps(0..=255).ranges(), vec![21..=249]);
+    }
+
+    #[test]
+    fn test_contains() {
+        let set = IntervalSet::from_ranges([1..=3, 7..=9]);
+
+        assert!(set.contains(1));
+        assert!(set.contains(8));
+        assert!(!set.contains(5));
+        assert!(!set.contains(10));
+    }
+
+    #[test]
+    fn test_set_algebra() {
+        let left = IntervalSet::from_ranges([1..=5, 10..=15]);
+        let right = IntervalSet::from_ranges([4..=11, 14..=20]);
+
+        assert_eq!(left.union(&right).ranges(), vec![1..=20]);
+        assert_eq!(
+            left.intersection(&right).ranges(),
+            vec![4..=5, 10..=11, 14..=15]
+        );
+        assert_eq!(left.difference(&right).ranges(), vec![1..=3, 12..=13]);
+        assert_eq!(right.difference(&left).ranges(), vec![6..=9, 16..=20]);
+    }
+
+    #[test]
+    fn test_difference_splits_interval() {
+        let left = IntervalSet::from_ranges([-10..=10]);
+        let right = IntervalSet::from_ranges([-5..=-3, 0..=0, 8..=12]);
+
+        assert_eq!(
+            left.difference(&right).ranges(),
+            vec![-10..=-6, -2..=-1, 1..=7]
+        );
+    }
+
+    #[test]
+    fn test_gaps() {
+        let set = IntervalSet::from_ranges([2..=4, 8..=9]);
+
+        assert_eq!(set.gaps(1..=10).ranges(), vec![1..=1, 5..=7, 10..=10]);
+        assert_eq!(set.gaps(3..=8).ranges(), vec![5..=7]);
+    }
+}
@@ -0,0 +1,164 @@
+use crate::interval_set::{Interval, Section};
+use std::ops::RangeInclusive;
+
+// A static interval tree for stabbing and overlap queries, each answered in
+// O(min(n, k log n)) for k results: logarithmic when few assignments match,
+// and never worse than a scan.
+//
+// Entries are sorted by start and laid out as an implicit balanced search
+// tree: the middle entry of any slice is that subtree's root. Each root also
+// records the largest end in its subtree, so a query can skip any subtree
+// that ends before the queried range begins.
+#[derive(Clone, Debug)]
+pub struct IntervalTree<T: Section, V> {
+    entries: Vec<(Interval<T>, V)>,
+    max_ends: Vec<T>,
+}
+
+impl<T: Section, V> IntervalTree<T, V> {
+    // Empty ranges are skipped.
+    pub fn new<I>(entries: I) -> Self
+    where
+        I: IntoIterator<Item = (RangeInclusive<T>, V)>,
+    {
+        let mut entries: Vec<(Interval<T>, V)> = entries
+            .into_iter()
+            .filter_map(|(range, value)| Some((Interval::try_from(range).ok()?, value)))
+            .collect();
+
+        entries.sort_by_key(|(interval, _)| *interval);
+
+        let mut max_ends: Vec<T> = entries.iter().map(|(interval, _)| interval.end()).collect();
+        build_max_ends(&entries, &mut max_ends, 0, entries.len());
+
+        IntervalTree { entries, max_ends }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    // Every value whose range contains the section.
+    pub fn stab(&self, section: T) -> Vec<&V> {
+        self.overlapping(section..=section)
+    }
+
+    // Every value whose range shares at least one section with the query,
+    // in order of range.
+    pub fn overlapping(&self, range: RangeInclusive<T>) -> Vec<&V> {
+        let mut results = Vec::new();
+
+        if let Ok(query) = Interval::try_from(range) {
+            self.collect_overlapping(&query, 0, self.entries.len(), &mut results);
+        }
+
+        results
+    }
+
+    fn collect_overlapping<'a>(
+        &'a self,
+        query: &Interval<T>,
+        low: usize,
+        high: usize,
+        results: &mut Vec<&'a V>,
+    ) {
+        if low >= high {
+            return;
+        }
+
+        let mid = low + (high - low) / 2;
+        if self.max_ends[mid] < query.start() {
+            return;
+        }
+
+        self.collect_overlapping(query, low, mid, results);
+
+        let (interval, value) = &self.entries[mid];
+        if interval.start() > query.end() {
+            // Everything to the right starts even later.
+            return;
+        }
+
+        if interval.intersects(query) {
+            results.push(value);
+        }
+
+        self.collect_overlapping(query, mid + 1, high, results);
+    }
+}
+
+fn build_max_ends<T: Section, V>(
+    entries: &[(Interval<T>, V)],
+    max_ends: &mut [T],
+    low: usize,
+    high: usize,
+) -> Option<T> {
+    if low >= high {
+        return None;
+    }
+
+    let mid = low + (high - low) / 2;
+    let left = build_max_ends(entries, max_ends, low, mid);
+    let right = build_max_ends(entries, max_ends, mid + 1, high);
+
+    let max_end = [left, right]
+        .into_iter()
+        .flatten()
+        .fold(entries[mid].0.end(), T::max);
+    max_ends[mid] = max_end;
+
+    Some(max_end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn brute_force(ranges: &[RangeInclusive<i32>], query: RangeInclusive<i32>) -> Vec<usize> {
+        ranges
+            .iter()
+            .enumerate()
+            .filter(|(_, range)| range.start() <= query.end() && query.start() <= range.end())
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    #[test]
+    fn test_stab() {
+        let tree = IntervalTree::new([(2..=4, 'a'), (6..=8, 'b'), (3..=7, 'c')]);
+
+        assert_eq!(tree.stab(3), vec![&'a', &'c']);
+        assert_eq!(tree.stab(5), vec![&'c']);
+        assert!(tree.stab(9).is_empty());
+    }
+
+    #[test]
+    fn test_overlapping_matches_brute_force() {
+        let ranges: Vec<RangeInclusive<i32>> = (0..200)
+            .map(|i: i32| {
+                let start = (i * 37) % 101 - 50;
+                start..=start + (i * 13) % 17
+            })
+            .collect();
+
+        let tree = IntervalTree::new(ranges.iter().cloned().zip(0..));
+
+        for start in -60..60 {
+            for width in [0, 3, 20] {
+                let query = start..=start + width;
+                let mut found: Vec<usize> = tree
+                    .overlapping(query.clone())
+                    .into_iter()
+                    .copied()
+                    .collect();
+                found.sort();
+
+                assert_eq!(found, brute_force(&ranges, query));
+            }
+        }
+    }
+}
@@ -1,10 +1,28 @@
+pub mod conflict_graph;
+pub mod coverage;
+pub mod diagram;
+pub mod interval_set;
+pub mod interval_tree;
 pub mod parse;
+pub mod planner;
+pub mod range_group;
 pub mod range_pair;
 pub mod util;
 
-use crate::parse::parse_range_pair;
-use crate::range_pair::{contains, intersects};
+use crate::conflict_graph::ConflictGraph;
+use crate::coverage::CoverageReport;
+use crate::diagram::{render_diagram, DiagramOptions};
+use crate::interval_set::{IntervalSet, Section};
+use crate::interval_tree::IntervalTree;
+use crate::parse::{parse_range_pair_with, parse_ranges_with, RangeValidation};
+use crate::planner::{plan_all_heuristic, plan_pair, ResolutionPlan};
+use crate::range_group::GroupSummary;
+use crate::range_pair::{RangeRelation, RelationSummary};
 use crate::util::read_lines;
+use std::fmt::Display;
+use std::num::ParseIntError;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -13,23 +31,465 @@ pub enum CountError {
     IoError(#[from] std::io::Error),
     #[error("Error parsing range pair: {0}")]
     ParseError(#[from] crate::parse::ParseError),
+    #[error("No way to separate the ranges on line {0}")]
+    UnresolvableLineError(usize),
+    #[error("No way to separate every range in the file")]
+    UnresolvableFileError,
 }
 
+// Counts pairs where one range contains the other, and pairs that overlap at
+// all. `summarize_relations` breaks these down further.
 pub fn count_overlapped_ranges(filename: &str) -> Result<(i32, i32), CountError> {
-    let mut contained_ranges_count = 0;
-    let mut intersected_ranges_count = 0;
+    let summary = summarize_relations(filename)?;
+
+    Ok((summary.contained() as i32, summary.intersected() as i32))
+}
+
+pub fn summarize_relations(filename: &str) -> Result<RelationSummary, CountError> {
+    summarize_relations_with::<u32>(filename, RangeValidation::default())
+}
+
+// Like `summarize_relations`, choosing the section type and how reversed
+// ranges are handled.
+pub fn summarize_relations_with<T>(
+    filename: &str,
+    validation: RangeValidation,
+) -> Result<RelationSummary, CountError>
+where
+    T: Section + FromStr<Err = ParseIntError>,
+{
+    let mut summary = RelationSummary::default();
 
     for line in read_lines(filename)? {
-        let (first_range, second_range) = parse_range_pair(line?)?;
+        let (first_range, second_range) = parse_range_pair_with::<T>(line?, validation)?;
+
+        summary.record(RangeRelation::classify(&first_range, &second_range));
+    }
 
-        if contains(&first_range, &second_range) || contains(&second_range, &first_range) {
-            contained_ranges_count += 1;
+    Ok(summary)
+}
+
+// Like `summarize_relations`, but for files whose lines can hold any number
+// of ranges.
+pub fn summarize_groups(filename: &str) -> Result<GroupSummary, CountError> {
+    summarize_groups_with::<u32>(filename, RangeValidation::default())
+}
+
+pub fn summarize_groups_with<T>(
+    filename: &str,
+    validation: RangeValidation,
+) -> Result<GroupSummary, CountError>
+where
+    T: Section + FromStr<Err = ParseIntError>,
+{
+    let mut summary = GroupSummary::default();
+
+    for line in read_lines(filename)? {
+        summary.record(&parse_ranges_with::<T>(line?, validation)?);
+    }
+
+    Ok(summary)
+}
+
+// The sections within the bound that no elf is assigned to.
+pub fn find_unassigned_sections<T>(
+    filename: &str,
+    bound: RangeInclusive<T>,
+    validation: RangeValidation,
+) -> Result<IntervalSet<T>, CountError>
+where
+    T: Section + FromStr<Err = ParseIntError>,
+{
+    Ok(IntervalSet::from_ranges(read_ranges(filename, validation)?).gaps(bound))
+}
+
+pub fn read_coverage_report<T>(
+    filename: &str,
+    validation: RangeValidation,
+) -> Result<CoverageReport<T>, CountError>
+where
+    T: Section + FromStr<Err = ParseIntError>,
+{
+    let ranges = read_ranges(filename, validation)?;
+
+    Ok(CoverageReport::from_ranges(ranges))
+}
+
+// Identifies one elf's assignment: the 1-based line it's on, and its 0-based
+// position on that line.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AssignmentId {
+    pub line: usize,
+    pub elf: usize,
+}
+
+// Indexes every assignment in the file for stabbing and overlap queries.
+pub fn read_assignment_index<T>(
+    filename: &str,
+    validation: RangeValidation,
+) -> Result<IntervalTree<T, AssignmentId>, CountError>
+where
+    T: Section + FromStr<Err = ParseIntError>,
+{
+    let mut entries = Vec::new();
+
+    for (i, line) in read_lines(filename)?.enumerate() {
+        let ranges = parse_ranges_with(line?, validation)?;
+
+        for (elf, range) in ranges.into_iter().enumerate() {
+            entries.push((range, AssignmentId { line: i + 1, elf }));
         }
+    }
+
+    Ok(IntervalTree::new(entries))
+}
+
+// A conflict graph over every assignment in the file. Nodes are numbered in
+// file order, line by line.
+pub fn read_conflict_graph<T>(
+    filename: &str,
+    validation: RangeValidation,
+) -> Result<ConflictGraph<T>, CountError>
+where
+    T: Section + FromStr<Err = ParseIntError>,
+{
+    Ok(ConflictGraph::new(read_ranges(filename, validation)?))
+}
+
+// For each line, the cheapest way to stop its two elves overlapping.
+pub fn plan_pair_resolutions<T>(
+    filename: &str,
+    validation: RangeValidation,
+) -> Result<Vec<ResolutionPlan<T>>, CountError>
+where
+    T: Section + FromStr<Err = ParseIntError> + Into<i128> + TryFrom<i128>,
+{
+    let mut plans = Vec::new();
+
+    for (i, line) in read_lines(filename)?.enumerate() {
+        let (first_range, second_range) = parse_range_pair_with::<T>(line?, validation)?;
+
+        plans.push(
+            plan_pair(&first_range, &second_range)
+                .ok_or(CountError::UnresolvableLineError(i + 1))?,
+        );
+    }
+
+    Ok(plans)
+}
+
+// A plan that stops any two elves in the file overlapping, from
+// `plan_all_heuristic`, so not always the cheapest. Ranges are in file order,
+// each line's ranges flattened out.
+pub fn plan_file_resolution<T>(
+    filename: &str,
+    validation: RangeValidation,
+) -> Result<ResolutionPlan<T>, CountError>
+where
+    T: Section + FromStr<Err = ParseIntError> + Into<i128> + TryFrom<i128>,
+{
+    plan_all_heuristic(&read_ranges(filename, validation)?).ok_or(CountError::UnresolvableFileError)
+}
+
+// Draws every assignment in the file, one row per elf.
+pub fn render_file_diagram<T>(
+    filename: &str,
+    options: &DiagramOptions<T>,
+    validation: RangeValidation,
+) -> Result<String, CountError>
+where
+    T: Section + FromStr<Err = ParseIntError> + Display + From<u8>,
+{
+    Ok(render_diagram(&read_ranges(filename, validation)?, options))
+}
+
+// Every range in the file, with each line's ranges flattened out.
+fn read_ranges<T>(
+    filename: &str,
+    validation: RangeValidation,
+) -> Result<Vec<RangeInclusive<T>>, CountError>
+where
+    T: Section + FromStr<Err = ParseIntError>,
+{
+    let mut ranges = Vec::new();
+
+    for line in read_lines(filename)? {
+        ranges.extend(parse_ranges_with(line?, validation)?);
+    }
+
+    Ok(ranges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::ParseError;
+    use crate::range_pair::intersects;
+
+    #[test]
+    fn test_count_overlapped_ranges() {
+        assert_eq!(
+            count_overlapped_ranges("fixtures/fixture.txt").unwrap(),
+            (2, 4)
+        );
+    }
+
+    #[test]
+    fn test_summarize_relations() {
+        let summary = summarize_relations("fixtures/fixture.txt").unwrap();
+
+        assert_eq!(
+            summary,
+            RelationSummary {
+                disjoint: 1,
+                adjacent: 1,
+                overlapping: 2,
+                left_contains_right: 1,
+                right_contains_left: 1,
+                equal: 0,
+                total_overlap: 10,
+            }
+        );
+    }
 
-        if intersects(&first_range, &second_range) {
-            intersected_ranges_count += 1;
+    #[test]
+    fn test_summarize_relations_with_reversed_ranges() {
+        let filename = "fixtures/reversed_ranges.txt";
+
+        assert!(matches!(
+            summarize_relations(filename),
+            Err(CountError::ParseError(ParseError::ReversedRangeError(_)))
+        ));
+
+        let normalized =
+            summarize_relations_with::<u32>(filename, RangeValidation::Normalize).unwrap();
+        assert_eq!(normalized.contained(), 2);
+
+        let empty =
+            summarize_relations_with::<u32>(filename, RangeValidation::TreatAsEmpty).unwrap();
+        assert_eq!(empty.contained(), 1);
+        assert_eq!(empty.disjoint, 1);
+    }
+
+    #[test]
+    fn test_summarize_groups() {
+        let summary = summarize_groups("fixtures/groups.txt").unwrap();
+
+        assert_eq!(
+            summary,
+            GroupSummary {
+                lines: 4,
+                containing_all: 2,
+                all_disjoint: 2,
+                overlapping_pairs: 8,
+            }
+        );
+    }
+
+    #[test]
+    fn test_summarize_groups_matches_pairs() {
+        let groups = summarize_groups("fixtures/fixture.txt").unwrap();
+        let pairs = summarize_relations("fixtures/fixture.txt").unwrap();
+
+        assert_eq!(groups.containing_all, pairs.contained());
+        assert_eq!(groups.overlapping_pairs, pairs.intersected());
+    }
+
+    #[test]
+    fn test_find_unassigned_sections() {
+        let unassigned =
+            find_unassigned_sections("fixtures/fixture.txt", 1..=10, RangeValidation::default())
+                .unwrap();
+
+        assert_eq!(unassigned.ranges(), vec![1..=1, 10..=10]);
+    }
+
+    #[test]
+    fn test_read_assignment_index() {
+        let index =
+            read_assignment_index("fixtures/fixture.txt", RangeValidation::default()).unwrap();
+
+        let mut covering: Vec<AssignmentId> = index.stab(9).into_iter().copied().collect();
+        covering.sort();
+        assert_eq!(covering, vec![AssignmentId { line: 3, elf: 1 }]);
+
+        let mut overlapping: Vec<AssignmentId> =
+            index.overlapping(1..=2).into_iter().copied().collect();
+        overlapping.sort();
+        assert_eq!(
+            overlapping,
+            vec![
+                AssignmentId { line: 1, elf: 0 },
+                AssignmentId { line: 2, elf: 0 },
+                AssignmentId { line: 4, elf: 0 },
+                AssignmentId { line: 6, elf: 0 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_coverage_report() {
+        let report =
+            read_coverage_report("fixtures/fixture.txt", RangeValidation::default()).unwrap();
+
+        assert_eq!(report.coverage_at(6), 8);
+        assert_eq!(report.max_overlap(), 8);
+        assert_eq!(report.max_overlap_sections().ranges(), vec![6..=6]);
+        assert_eq!(report.covered_more_than(6).ranges(), vec![4..=6]);
+    }
+
+    #[test]
+    fn test_read_conflict_graph() {
+        let graph =
+            read_conflict_graph::<u32>("fixtures/fixture.txt", RangeValidation::default()).unwrap();
+
+        assert_eq!(graph.node_count(), 12);
+        assert_eq!(graph.components().len(), 1);
+        assert_eq!(graph.shift_count(), 8);
+        assert_eq!(graph.largest_clique().len(), 8);
+    }
+
+    #[test]
+    fn test_render_file_diagram() {
+        let diagram = render_file_diagram::<u32>(
+            "fixtures/fixture.txt",
+            &DiagramOptions::default(),
+            RangeValidation::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            diagram,
+            std::fs::read_to_string("fixtures/fixture_diagram.txt").unwrap()
+        );
+        assert_eq!(
+            crate::diagram::parse_diagram::<u32>(&diagram).unwrap(),
+            read_ranges("fixtures/fixture.txt", RangeValidation::default()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_plan_pair_resolutions() {
+        let plans =
+            plan_pair_resolutions::<u32>("fixtures/fixture.txt", RangeValidation::default())
+                .unwrap();
+        let costs: Vec<u64> = plans.iter().map(ResolutionPlan::cost).collect();
+
+        assert_eq!(costs, vec![0, 0, 1, 6, 1, 3]);
+    }
+
+    #[test]
+    fn test_plan_file_resolution() {
+        let plan =
+            plan_file_resolution("fixtures/fixture.txt", RangeValidation::default()).unwrap();
+        let ranges: Vec<RangeInclusive<u32>> = plan.ranges.iter().map(|r| r.range()).collect();
+
+        for (i, first) in ranges.iter().enumerate() {
+            for second in &ranges[i + 1..] {
+                assert!(!intersects(first, second));
+            }
         }
     }
 
-    Ok((contained_ranges_count, intersected_ranges_count))
+    #[test]
+    fn test_read_lines_of_any_length() {
+        let filename = "fixtures/groups.txt";
+        let ranges = read_ranges::<u32>(filename, RangeValidation::default()).unwrap();
+        assert_eq!(ranges.len(), 11);
+
+        let index = read_assignment_index(filename, RangeValidation::default()).unwrap();
+        let mut covering: Vec<AssignmentId> = index.stab(8).into_iter().copied().collect();
+        covering.sort();
+        assert_eq!(
+            covering,
+            vec![
+                AssignmentId { line: 1, elf: 0 },
+                AssignmentId { line: 2, elf: 2 },
+                AssignmentId { line: 3, elf: 2 },
+            ]
+        );
+
+        let graph = read_conflict_graph::<u32>(filename, RangeValidation::default()).unwrap();
+        assert_eq!(graph.node_count(), 11);
+
+        let plan = plan_file_resolution::<u32>(filename, RangeValidation::default()).unwrap();
+        assert_eq!(plan.ranges.len(), 11);
+
+        let diagram = render_file_diagram::<u32>(
+            filename,
+            &DiagramOptions::default(),
+            RangeValidation::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            crate::diagram::parse_diagram::<u32>(&diagram).unwrap(),
+            ranges
+        );
+    }
+
+    #[test]
+    fn test_read_coverage_report_with_reversed_ranges() {
+        let filename = "fixtures/reversed_ranges.txt";
+
+        assert!(matches!(
+            read_coverage_report::<u32>(filename, RangeValidation::default()),
+            Err(CountError::ParseError(ParseError::ReversedRangeError(_)))
+        ));
+
+        let normalized = read_coverage_report::<u32>(filename, RangeValidation::Normalize).unwrap();
+        assert_eq!(normalized.coverage_at(4), 4);
+
+        let empty = read_coverage_report::<u32>(filename, RangeValidation::TreatAsEmpty).unwrap();
+        assert_eq!(empty.coverage_at(4), 3);
+    }
+
+    #[test]
+    fn test_read_signed_sections() {
+        let filename = "fixtures/signed.txt";
+
+        let summary =
+            summarize_relations_with::<i32>(filename, RangeValidation::default()).unwrap();
+        assert_eq!(summary.disjoint, 1);
+        assert_eq!(summary.overlapping, 1);
+        assert_eq!(summary.left_contains_right, 1);
+
+        let unassigned =
+            find_unassigned_sections(filename, -10..=5, RangeValidation::default()).unwrap();
+        assert_eq!(unassigned.ranges(), vec![-10..=-9, 4..=5]);
+
+        let report = read_coverage_report::<i32>(filename, RangeValidation::default()).unwrap();
+        assert_eq!(report.coverage_at(-2), 3);
+        assert_eq!(report.max_overlap_sections().ranges(), vec![0..=0]);
+
+        let index = read_assignment_index(filename, RangeValidation::default()).unwrap();
+        let covering: Vec<AssignmentId> = index.stab(-7).into_iter().copied().collect();
+        assert_eq!(covering, vec![AssignmentId { line: 2, elf: 0 }]);
+
+        let graph = read_conflict_graph::<i32>(filename, RangeValidation::default()).unwrap();
+        assert_eq!(graph.components().len(), 2);
+        assert_eq!(graph.largest_clique().len(), 4);
+
+        let plans = plan_pair_resolutions::<i32>(filename, RangeValidation::default()).unwrap();
+        let costs: Vec<u64> = plans.iter().map(ResolutionPlan::cost).collect();
+        assert_eq!(costs, vec![2, 0, 3]);
+
+        let plan = plan_file_resolution::<i32>(filename, RangeValidation::default()).unwrap();
+        let ranges: Vec<RangeInclusive<i32>> = plan.ranges.iter().map(|r| r.range()).collect();
+        for (i, first) in ranges.iter().enumerate() {
+            for second in &ranges[i + 1..] {
+                assert!(!intersects(first, second));
+            }
+        }
+
+        let diagram = render_file_diagram::<i32>(
+            filename,
+            &DiagramOptions::default(),
+            RangeValidation::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            crate::diagram::parse_diagram::<i32>(&diagram).unwrap(),
+            read_ranges::<i32>(filename, RangeValidation::default()).unwrap()
+        );
+    }
 }
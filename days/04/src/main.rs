@@ -1,10 +1,17 @@
-use day04::count_overlapped_ranges;
+use day04::summarize_relations;
 
 pub fn main() {
-    match count_overlapped_ranges("input.txt") {
-        Ok((contained_ranges_count, overlapped_ranges_count)) => {
-            println!("{} ranges are contained", contained_ranges_count);
-            println!("{} ranges overlap", overlapped_ranges_count);
+    match summarize_relations("input.txt") {
+        Ok(summary) => {
+            println!("{} ranges are contained", summary.contained());
+            println!("{} ranges overlap", summary.intersected());
+            println!(
+                "({} identical, {} strictly contained, {} partially overlapping, {} adjacent)",
+                summary.equal,
+                summary.left_contains_right + summary.right_contains_left,
+                summary.overlapping,
+                summary.adjacent
+            );
         }
         Err(e) => println!("Error counting overlapped ranges: {}", e),
     }
@@ -1,4 +1,6 @@
+use std::num::ParseIntError;
 use std::ops::RangeInclusive;
+use std::str::FromStr;
 
 use thiserror::Error;
 
@@ -8,36 +10,167 @@ pub enum ParseError {
     RangePairError(String),
     #[error("Error parsing number: {0}")]
     NumberError(#[from] std::num::ParseIntError),
+    #[error("Range ends before it starts: {0}")]
+    ReversedRangeError(String),
+}
+
+// What to do with a range like `5-2` whose end comes before its start. Every
+// reader rejects them unless it's told otherwise.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RangeValidation {
+    #[default]
+    Reject,
+    // Swap the ends, so `5-2` becomes `2..=5`.
+    Normalize,
+    // Keep `5..=2` as is. It's empty, so it overlaps and contains nothing.
+    TreatAsEmpty,
 }
 
 pub fn parse_range_pair(
     line: String,
 ) -> Result<(RangeInclusive<u32>, RangeInclusive<u32>), ParseError> {
-    let parts = line.split(',').collect::<Vec<&str>>();
+    parse_range_pair_with(line, RangeValidation::default())
+}
+
+// Like `parse_range_pair`, but over any integer type, including signed ones:
+// `-3--1` is the range from -3 to -1.
+pub fn parse_range_pair_with<T>(
+    line: String,
+    validation: RangeValidation,
+) -> Result<(RangeInclusive<T>, RangeInclusive<T>), ParseError>
+where
+    T: FromStr<Err = ParseIntError> + PartialOrd,
+{
+    let ranges = parse_ranges_with(line.clone(), validation)?;
+
+    match <[RangeInclusive<T>; 2]>::try_from(ranges) {
+        Ok([first_range, second_range]) => Ok((first_range, second_range)),
+        Err(_) => Err(ParseError::RangePairError(line)),
+    }
+}
+
+// Parses a line of any number of comma-separated ranges, like `2-4,6-8,3-3`.
+pub fn parse_ranges(line: String) -> Result<Vec<RangeInclusive<u32>>, ParseError> {
+    parse_ranges_with(line, RangeValidation::default())
+}
+
+pub fn parse_ranges_with<T>(
+    line: String,
+    validation: RangeValidation,
+) -> Result<Vec<RangeInclusive<T>>, ParseError>
+where
+    T: FromStr<Err = ParseIntError> + PartialOrd,
+{
+    let mut ranges = Vec::new();
+
+    for part in line.split(',') {
+        let Some((start, end)) = split_range(part) else {
+            return Err(ParseError::RangePairError(line));
+        };
 
-    if parts.len() != 2 {
-        return Err(ParseError::RangePairError(line));
+        match validate(start.parse::<T>()?, end.parse::<T>()?, validation) {
+            Some(range) => ranges.push(range),
+            None => return Err(ParseError::ReversedRangeError(line)),
+        }
     }
 
-    let first_range_parts = parts[0].split('-').collect::<Vec<&str>>();
-    let second_range_parts = parts[1].split('-').collect::<Vec<&str>>();
+    Ok(ranges)
+}
+
+// Splits `start-end` on the dash between the two numbers, skipping a leading
+// minus sign on the start.
+fn split_range(range: &str) -> Option<(&str, &str)> {
+    let separator = range.get(1..)?.find('-')? + 1;
+
+    Some((&range[..separator], &range[separator + 1..]))
+}
 
-    if first_range_parts.len() != 2 {
-        return Err(ParseError::RangePairError(line));
+fn validate<T: PartialOrd>(
+    start: T,
+    end: T,
+    validation: RangeValidation,
+) -> Option<RangeInclusive<T>> {
+    if start <= end {
+        return Some(start..=end);
     }
 
-    if second_range_parts.len() != 2 {
-        return Err(ParseError::RangePairError(line));
+    match validation {
+        RangeValidation::Reject => None,
+        RangeValidation::Normalize => Some(end..=start),
+        RangeValidation::TreatAsEmpty => Some(start..=end),
     }
+}
 
-    let first_range_start = first_range_parts[0].parse::<u32>()?;
-    let first_range_end = first_range_parts[1].parse::<u32>()?;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let second_range_start = second_range_parts[0].parse::<u32>()?;
-    let second_range_end = second_range_parts[1].parse::<u32>()?;
+    #[test]
+    fn test_parse_range_pair() {
+        assert_eq!(
+            parse_range_pair("2-4,6-8".to_string()).unwrap(),
+            (2..=4, 6..=8)
+        );
+        assert!(matches!(
+            parse_range_pair("2-4".to_string()),
+            Err(ParseError::RangePairError(_))
+        ));
+        assert!(matches!(
+            parse_range_pair("2-4-5,6-8".to_string()),
+            Err(ParseError::NumberError(_))
+        ));
+        assert!(matches!(
+            parse_range_pair("-2-4,6-8".to_string()),
+            Err(ParseError::NumberError(_))
+        ));
+    }
 
-    let first_range = first_range_start..=first_range_end;
-    let second_range = second_range_start..=second_range_end;
+    #[test]
+    fn test_parse_ranges() {
+        assert_eq!(
+            parse_ranges("2-4,6-8,3-3".to_string()).unwrap(),
+            vec![2..=4, 6..=8, 3..=3]
+        );
+        assert_eq!(parse_ranges("5-9".to_string()).unwrap(), vec![5..=9]);
+        assert!(matches!(
+            parse_ranges("".to_string()),
+            Err(ParseError::RangePairError(_))
+        ));
+        assert!(matches!(
+            parse_ranges("2-4,,6-8".to_string()),
+            Err(ParseError::RangePairError(_))
+        ));
+    }
 
-    Ok((first_range, second_range))
+    #[test]
+    fn test_parse_reversed_range() {
+        let line = "5-2,6-8".to_string();
+
+        match parse_range_pair(line.clone()) {
+            Err(ParseError::ReversedRangeError(offending)) => assert_eq!(offending, line),
+            other => panic!("expected a reversed range error, got {:?}", other),
+        }
+
+        assert_eq!(
+            parse_range_pair_with::<u32>(line.clone(), RangeValidation::Normalize).unwrap(),
+            (2..=5, 6..=8)
+        );
+
+        let (first_range, _) =
+            parse_range_pair_with::<u32>(line, RangeValidation::TreatAsEmpty).unwrap();
+        assert!(first_range.is_empty());
+    }
+
+    #[test]
+    fn test_parse_signed_range_pair() {
+        assert_eq!(
+            parse_range_pair_with::<i32>("-3--1,-1-4".to_string(), RangeValidation::Reject)
+                .unwrap(),
+            (-3..=-1, -1..=4)
+        );
+        assert!(matches!(
+            parse_range_pair_with::<i32>("-1--3,0-0".to_string(), RangeValidation::Reject),
+            Err(ParseError::ReversedRangeError(_))
+        ));
+    }
 }
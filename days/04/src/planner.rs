@@ -0,0 +1,667 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::ops::{Bound, RangeInclusive};
+
+// What to do with one assignment so it no longer overlaps its neighbours.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Adjustment<T> {
+    Keep,
+    // Trim sections off either end; the new range lies within the old one.
+    Shrink(RangeInclusive<T>),
+    // Move the whole range, keeping its length.
+    Shift(RangeInclusive<T>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlannedRange<T> {
+    pub original: RangeInclusive<T>,
+    pub adjustment: Adjustment<T>,
+}
+
+impl<T> PlannedRange<T>
+where
+    T: Copy + Into<i128>,
+{
+    // The range the elf ends up with.
+    pub fn range(&self) -> RangeInclusive<T> {
+        match &self.adjustment {
+            Adjustment::Keep => self.original.clone(),
+            Adjustment::Shrink(range) | Adjustment::Shift(range) => range.clone(),
+        }
+    }
+
+    // Sections changed: those the elf gives up plus those newly assigned.
+    pub fn cost(&self) -> u64 {
+        let (start, end) = bounds(&self.original);
+        let (new_start, new_end) = bounds(&self.range());
+        let shared = (end.min(new_end) - start.max(new_start) + 1).max(0);
+
+        ((end - start + 1) + (new_end - new_start + 1) - 2 * shared) as u64
+    }
+}
+
+// The adjusted ranges, in the order they were given.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResolutionPlan<T> {
+    pub ranges: Vec<PlannedRange<T>>,
+}
+
+impl<T> ResolutionPlan<T>
+where
+    T: Copy + Into<i128>,
+{
+    pub fn cost(&self) -> u64 {
+        self.ranges.iter().map(PlannedRange::cost).sum()
+    }
+}
+
+// The cheapest plan that leaves the two ranges disjoint. Returns `None` if
+// either range is empty, or if every plan would need a section the type
+// can't represent.
+//
+// Whichever range ends up first, the plan splits the sections at some point
+// `p` and fits one range at or before it and the other after it. Each side's
+// cost is constant except between its range's own start and end, where it
+// changes by one section per step, so the cheapest split is at one end of
+// those stretches.
+pub fn plan_pair<T>(
+    first: &RangeInclusive<T>,
+    second: &RangeInclusive<T>,
+) -> Option<ResolutionPlan<T>>
+where
+    T: Copy + Ord + Into<i128> + TryFrom<i128>,
+{
+    if first.is_empty() || second.is_empty() {
+        return None;
+    }
+
+    let mut best: Option<ResolutionPlan<T>> = None;
+
+    for (before, after, swapped) in [(first, second, false), (second, first, true)] {
+        let (before_start, before_end) = bounds(before);
+        let (after_start, after_end) = bounds(after);
+
+        for split in [
+            before_start - 1,
+            before_start,
+            before_end,
+            after_start - 1,
+            after_end - 1,
+            after_end,
+        ] {
+            let (Some(before_adjustment), Some(after_adjustment)) =
+                (fit_before(before, split), fit_after(after, split))
+            else {
+                continue;
+            };
+
+            let mut ranges = vec![
+                PlannedRange {
+                    original: before.clone(),
+                    adjustment: before_adjustment,
+                },
+                PlannedRange {
+                    original: after.clone(),
+                    adjustment: after_adjustment,
+                },
+            ];
+            if swapped {
+                ranges.reverse();
+            }
+
+            let plan = ResolutionPlan { ranges };
+            if best.as_ref().is_none_or(|best| plan.cost() < best.cost()) {
+                best = Some(plan);
+            }
+        }
+    }
+
+    best
+}
+
+// A cheap plan leaving every range pairwise disjoint, though not always the
+// cheapest. Returns `None` if any range is empty, or if there's no room within
+// the type's bounds for the ranges it moves.
+//
+// Each range is either trimmed to part of itself or moved clear of all the
+// others, since moving it by less than its length changes more sections than
+// trimming off the same amount. Ranges are trimmed in order of start, except
+// that a range may wait while the ranges right after it are nested inside
+// it, and be trimmed after some of them. Taking ranges in that order, the
+// plan keeps the cheapest way to reach each frontier (the last section
+// trimmed ranges hold so far) with each range left waiting. A trimmed
+// range's new end only needs trying at its old end, just before a later
+// range starts, or straight after the frontier.
+//
+// That makes this the cheapest of those plans, which covers every plan for
+// two ranges. With more, the cheapest plan can need several ranges waiting at
+// once, and tracking every set of them grows exponentially, so this is a
+// heuristic.
+pub fn plan_all_heuristic<T>(ranges: &[RangeInclusive<T>]) -> Option<ResolutionPlan<T>>
+where
+    T: Copy + Ord + Into<i128> + TryFrom<i128>,
+{
+    if ranges.iter().any(|range| range.is_empty()) {
+        return None;
+    }
+
+    let mut order: Vec<usize> = (0..ranges.len()).collect();
+    order.sort_by_key(|&i| bounds(&ranges[i]));
+    let starts: BTreeSet<i128> = ranges.iter().map(|range| bounds(range).0).collect();
+    let length = |i: usize| {
+        let (start, end) = bounds(&ranges[i]);
+        (end - start + 1) as u64
+    };
+    let end_of = |position: usize| order.get(position).map(|&i| bounds(&ranges[i]).1);
+    let mut costs: BTreeMap<State, u64> = BTreeMap::from([((None, None), 0)]);
+    let mut steps: Vec<BTreeMap<State, Step>> = Vec::with_capacity(2 * order.len() + 1);
+
+    for (position, &i) in order.iter().enumerate() {
+        let end = bounds(&ranges[i]).1;
+
+        // Settle the waiting range unless this one is nested inside it.
+        costs = take_step(&mut steps, settle(ranges, &starts, &costs, Some(end)));
+
+        let mut step = BTreeMap::new();
+        for (&state, &cost) in &costs {
+            let (frontier, waiting) = state;
+            let moved = Step {
+                cost: cost + 2 * length(i),
+                previous: state,
+                placed: None,
+            };
+            record(&mut step, state, moved);
+
+            for (block, trimmed) in trims(ranges, &starts, i, frontier) {
+                let trimmed = Step {
+                    cost: cost + trimmed,
+                    previous: state,
+                    placed: Some(block),
+                };
+                record(&mut step, (Some(block.2), waiting), trimmed);
+            }
+
+            if waiting.is_none() && end_of(position + 1).is_some_and(|next| next < end) {
+                let waits = Step {
+                    cost,
+                    previous: state,
+                    placed: None,
+                };
+                record(&mut step, (frontier, Some(i)), waits);
+            }
+        }
+
+        costs = take_step(&mut steps, step);
+    }
+
+    // Whatever is still waiting is trimmed after everything else or moved.
+    take_step(&mut steps, settle(ranges, &starts, &costs, None));
+
+    let mut state = steps
+        .last()
+        .and_then(|step| step.iter().min_by_key(|(_, step)| step.cost))
+        .map(|(&state, _)| state)?;
+    let mut kept = vec![None; ranges.len()];
+
+    for step in steps.iter().rev() {
+        let Step {
+            previous, placed, ..
+        } = step[&state];
+
+        if let Some((i, first, last)) = placed {
+            kept[i] = Some((first, last));
+        }
+
+        state = previous;
+    }
+
+    // Moved ranges go past every original range, or before them if there's
+    // no room after.
+    let mut after = ranges
+        .iter()
+        .map(|range| bounds(range).1)
+        .max()
+        .unwrap_or(0)
+        + 1;
+    let mut before = ranges
+        .iter()
+        .map(|range| bounds(range).0)
+        .min()
+        .unwrap_or(0)
+        - 1;
+    let mut planned = Vec::with_capacity(ranges.len());
+
+    for (range, kept) in ranges.iter().zip(kept) {
+        let (start, end) = bounds(range);
+        let length = end - start + 1;
+
+        let adjustment = match kept {
+            Some(kept) if kept == (start, end) => Adjustment::Keep,
+            Some((first, last)) => Adjustment::Shrink(to_range(first, last)?),
+            None => match to_range(after, after + length - 1) {
+                Some(moved) => {
+                    after += length;
+                    Adjustment::Shift(moved)
+                }
+                None => {
+                    before -= length;
+                    Adjustment::Shift(to_range(before + 1, before + length)?)
+                }
+            },
+        };
+
+        planned.push(PlannedRange {
+            original: range.clone(),
+            adjustment,
+        });
+    }
+
+    Some(ResolutionPlan { ranges: planned })
+}
+
+// The frontier, and the range left waiting if there is one.
+type State = (Option<i128>, Option<usize>);
+
+// A range trimmed to `first..=last`.
+type Block = (usize, i128, i128);
+
+// How `plan_all_heuristic` reached a state: from which one, and which range
+// was trimmed on the way, if any. Ranges never trimmed are moved.
+#[derive(Clone, Copy, Debug)]
+struct Step {
+    cost: u64,
+    previous: State,
+    placed: Option<Block>,
+}
+
+fn record(steps: &mut BTreeMap<State, Step>, state: State, step: Step) {
+    if steps.get(&state).is_none_or(|best| step.cost < best.cost) {
+        steps.insert(state, step);
+    }
+}
+
+// Drops every state beaten by another that's no further along and costs no
+// more, with the same range or none left waiting. That one can follow any
+// plan from the beaten state, just leaving out the waiting range.
+fn prune(steps: &mut BTreeMap<State, Step>) {
+    let mut cheapest_free = u64::MAX;
+    let mut cheapest_waiting: HashMap<usize, u64> = HashMap::new();
+
+    // States are visited in order of frontier, with none waiting first.
+    steps.retain(|&(_, waiting), step| {
+        let cheapest = match waiting {
+            None => &mut cheapest_free,
+            Some(waiting) => {
+                let cheapest = cheapest_waiting.entry(waiting).or_insert(u64::MAX);
+                *cheapest = (*cheapest).min(cheapest_free);
+                cheapest
+            }
+        };
+
+        let kept = step.cost < *cheapest;
+        if kept {
+            *cheapest = step.cost;
+        }
+
+        kept
+    });
+}
+
+// The ways to trim range `i` to lie after the frontier, with their costs.
+fn trims<T>(
+    ranges: &[RangeInclusive<T>],
+    starts: &BTreeSet<i128>,
+    i: usize,
+    frontier: Option<i128>,
+) -> Vec<(Block, u64)>
+where
+    T: Copy + Into<i128>,
+{
+    let (start, end) = bounds(&ranges[i]);
+    let first = frontier.map_or(start, |frontier| start.max(frontier + 1));
+    if first > end {
+        return Vec::new();
+    }
+
+    let before_starts = starts
+        .range((Bound::Excluded(first), Bound::Included(end)))
+        .map(|start| start - 1);
+
+    [first, end]
+        .into_iter()
+        .chain(before_starts)
+        .map(|last| ((i, first, last), (first - start + end - last) as u64))
+        .collect()
+}
+
+// Every way to settle the waiting range before the next range, which ends at
+// `next_end`: trimming it now, or leaving it waiting if the next range is
+// nested inside it. Otherwise, or once there's no room left to trim it, it's
+// moved.
+fn settle<T>(
+    ranges: &[RangeInclusive<T>],
+    starts: &BTreeSet<i128>,
+    costs: &BTreeMap<State, u64>,
+    next_end: Option<i128>,
+) -> BTreeMap<State, Step>
+where
+    T: Copy + Into<i128>,
+{
+    let mut step = BTreeMap::new();
+
+    for (&state, &cost) in costs {
+        let (frontier, waiting) = state;
+        let Some(waiting) = waiting else {
+            record(
+                &mut step,
+                state,
+                Step {
+                    cost,
+                    previous: state,
+                    placed: None,
+                },
+            );
+            continue;
+        };
+
+        let (start, end) = bounds(&ranges[waiting]);
+        let trims = trims(ranges, starts, waiting, frontier);
+
+        if !trims.is_empty() && next_end.is_some_and(|next_end| next_end < end) {
+            record(
+                &mut step,
+                state,
+                Step {
+                    cost,
+                    previous: state,
+                    placed: None,
+                },
+            );
+        } else {
+            let moved = Step {
+                cost: cost + 2 * (end - start + 1) as u64,
+                previous: state,
+                placed: None,
+            };
+            record(&mut step, (frontier, None), moved);
+        }
+
+        for (block, trimmed) in trims {
+            let trimmed = Step {
+                cost: cost + trimmed,
+                previous: state,
+                placed: Some(block),
+            };
+            record(&mut step, (Some(block.2), None), trimmed);
+        }
+    }
+
+    step
+}
+
+// Prunes the step, keeps it for tracing the plan back, and returns the cost
+// of each state it reaches.
+fn take_step(
+    steps: &mut Vec<BTreeMap<State, Step>>,
+    mut step: BTreeMap<State, Step>,
+) -> BTreeMap<State, u64> {
+    prune(&mut step);
+    let costs = step
+        .iter()
+        .map(|(&state, step)| (state, step.cost))
+        .collect();
+    steps.push(step);
+
+    costs
+}
+
+// The cheapest way to fit the range entirely at or before `split`.
+fn fit_before<T>(range: &RangeInclusive<T>, split: i128) -> Option<Adjustment<T>>
+where
+    T: Copy + Ord + Into<i128> + TryFrom<i128>,
+{
+    let (start, end) = bounds(range);
+
+    if end <= split {
+        Some(Adjustment::Keep)
+    } else if start <= split {
+        Some(Adjustment::Shrink(to_range(start, split)?))
+    } else {
+        Some(Adjustment::Shift(to_range(split - (end - start), split)?))
+    }
+}
+
+// The cheapest way to fit the range entirely after `split`.
+fn fit_after<T>(range: &RangeInclusive<T>, split: i128) -> Option<Adjustment<T>>
+where
+    T: Copy + Ord + Into<i128> + TryFrom<i128>,
+{
+    let (start, end) = bounds(range);
+
+    if start > split {
+        Some(Adjustment::Keep)
+    } else if end > split {
+        Some(Adjustment::Shrink(to_range(split + 1, end)?))
+    } else {
+        Some(Adjustment::Shift(to_range(
+            split + 1,
+            split + 1 + (end - start),
+        )?))
+    }
+}
+
+fn bounds<T: Copy + Into<i128>>(range: &RangeInclusive<T>) -> (i128, i128) {
+    ((*range.start()).into(), (*range.end()).into())
+}
+
+fn to_range<T: TryFrom<i128>>(start: i128, end: i128) -> Option<RangeInclusive<T>> {
+    Some(T::try_from(start).ok()?..=T::try_from(end).ok()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolved(plan: &ResolutionPlan<u32>) -> Vec<RangeInclusive<u32>> {
+        plan.ranges.iter().map(PlannedRange::range).collect()
+    }
+
+    #[test]
+    fn test_plan_pair_disjoint() {
+        let plan = plan_pair(&(2..=4), &(6..=8)).unwrap();
+
+        assert_eq!(plan.cost(), 0);
+        assert_eq!(resolved(&plan), vec![2..=4, 6..=8]);
+    }
+
+    #[test]
+    fn test_plan_pair_overlap_shrinks() {
+        let plan = plan_pair(&(5..=7), &(7..=9)).unwrap();
+
+        assert_eq!(plan.cost(), 1);
+        let ranges = resolved(&plan);
+        assert!(!ranges[0].contains(&7) || !ranges[1].contains(&7));
+    }
+
+    #[test]
+    fn test_plan_pair_containment_shifts() {
+        let plan = plan_pair(&(1..=10), &(4..=5)).unwrap();
+
+        assert_eq!(plan.cost(), 4);
+        assert_eq!(plan.ranges[0].adjustment, Adjustment::Keep);
+        assert!(matches!(plan.ranges[1].adjustment, Adjustment::Shift(_)));
+    }
+
+    #[test]
+    fn test_plan_pair_respects_type_bounds() {
+        let plan = plan_pair(&(0u32..=0), &(0u32..=0)).unwrap();
+
+        assert_eq!(plan.cost(), 2);
+        assert_eq!(resolved(&plan), vec![0..=0, 1..=1]);
+    }
+
+    // Every range within 0..=20 the elf could end up with: part of the
+    // original, or the original moved.
+    fn candidates(range: &RangeInclusive<u32>) -> Vec<RangeInclusive<u32>> {
+        let (start, end) = (*range.start(), *range.end());
+        let mut candidates: Vec<RangeInclusive<u32>> = (start..=end)
+            .flat_map(|first| (first..=end).map(move |last| first..=last))
+            .collect();
+        candidates.extend((0..=20 - (end - start)).map(|first| first..=first + (end - start)));
+
+        candidates
+    }
+
+    fn change(original: &RangeInclusive<u32>, range: &RangeInclusive<u32>) -> u64 {
+        PlannedRange {
+            original: original.clone(),
+            adjustment: Adjustment::Shift(range.clone()),
+        }
+        .cost()
+    }
+
+    fn small_ranges(limit: u32) -> Vec<RangeInclusive<u32>> {
+        (0..limit)
+            .flat_map(|start| (start..limit).map(move |end| start..=end))
+            .collect()
+    }
+
+    fn assert_disjoint(plan: &ResolutionPlan<u32>) {
+        let ranges = resolved(plan);
+
+        for (i, first) in ranges.iter().enumerate() {
+            for second in &ranges[i + 1..] {
+                assert!(first.end() < second.start() || second.end() < first.start());
+            }
+        }
+    }
+
+    #[test]
+    fn test_plan_pair_both_shrink() {
+        let plan = plan_pair(&(0..=1), &(0..=1)).unwrap();
+
+        assert_eq!(plan.cost(), 2);
+        assert_eq!(resolved(&plan), vec![0..=0, 1..=1]);
+    }
+
+    #[test]
+    fn test_plan_pair_matches_brute_force() {
+        let ranges = small_ranges(8);
+
+        for first in &ranges {
+            for second in &ranges {
+                let cheapest = candidates(first)
+                    .iter()
+                    .flat_map(|new_first| {
+                        candidates(second)
+                            .into_iter()
+                            .filter(|new_second| {
+                                new_first.end() < new_second.start()
+                                    || new_second.end() < new_first.start()
+                            })
+                            .map(|new_second| {
+                                change(first, new_first) + change(second, &new_second)
+                            })
+                    })
+                    .min()
+                    .unwrap();
+
+                let plan = plan_pair(first, second).unwrap();
+                assert_disjoint(&plan);
+                assert_eq!(plan.cost(), cheapest, "{:?} {:?}", first, second);
+            }
+        }
+    }
+
+    #[test]
+    fn test_plan_all_heuristic() {
+        let plan = plan_all_heuristic(&[3..=7, 1..=4, 6..=6]).unwrap();
+
+        assert_disjoint(&plan);
+        assert_eq!(plan.cost(), 4);
+    }
+
+    #[test]
+    fn test_plan_all_heuristic_gives_up_long_range_start() {
+        let mut ranges = vec![1..=100];
+        ranges.extend((2..=50).map(|section| section..=section));
+
+        let plan = plan_all_heuristic(&ranges).unwrap();
+
+        assert_eq!(plan.cost(), 50);
+        assert_eq!(plan.ranges[0].range(), 51..=100);
+        assert!(plan.ranges[1..]
+            .iter()
+            .all(|range| range.adjustment == Adjustment::Keep));
+    }
+
+    #[test]
+    fn test_plan_all_heuristic_moves_clear_of_the_others() {
+        let plan = plan_all_heuristic(&[0..=4, 0..=4, 0..=4, 0..=4]).unwrap();
+
+        assert_disjoint(&plan);
+        assert_eq!(plan.cost(), 15);
+    }
+
+    // The cheapest plan that trims each range to any part of itself or moves
+    // it clear of the others.
+    fn brute_force(ranges: &[RangeInclusive<u32>], trimmed: &mut Vec<RangeInclusive<u32>>) -> u64 {
+        let Some((range, rest)) = ranges.split_first() else {
+            return 0;
+        };
+        let (start, end) = (*range.start(), *range.end());
+        let mut cheapest = 2 * u64::from(end - start + 1) + brute_force(rest, trimmed);
+
+        for first in start..=end {
+            for last in first..=end {
+                if trimmed
+                    .iter()
+                    .any(|other| *other.start() <= last && first <= *other.end())
+                {
+                    continue;
+                }
+
+                trimmed.push(first..=last);
+                let cost = u64::from((end - start) - (last - first)) + brute_force(rest, trimmed);
+                trimmed.pop();
+
+                cheapest = cheapest.min(cost);
+            }
+        }
+
+        cheapest
+    }
+
+    // The cheapest plan keeps 3..=3 and 4..=4, then trims 3..=5 to 5..=5 and
+    // 1..=8 to 6..=8, so both of those wait at once.
+    #[test]
+    fn test_plan_all_heuristic_can_miss_the_cheapest() {
+        let given = [1..=8, 3..=3, 3..=5, 4..=4];
+
+        let plan = plan_all_heuristic(&given).unwrap();
+        assert_disjoint(&plan);
+        assert_eq!(brute_force(&given, &mut Vec::new()), 7);
+        assert_eq!(plan.cost(), 8);
+    }
+
+    #[test]
+    fn test_plan_all_heuristic_matches_brute_force_for_three_ranges() {
+        let ranges = small_ranges(6);
+
+        for first in &ranges {
+            for second in &ranges {
+                for third in &ranges {
+                    let given = [first.clone(), second.clone(), third.clone()];
+
+                    let plan = plan_all_heuristic(&given).unwrap();
+                    assert_disjoint(&plan);
+                    assert_eq!(
+                        plan.cost(),
+                        brute_force(&given, &mut Vec::new()),
+                        "{:?}",
+                        given
+                    );
+                }
+            }
+        }
+    }
+}
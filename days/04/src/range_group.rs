@@ -0,0 +1,104 @@
+use crate::range_pair::contains;
+use std::ops::RangeInclusive;
+
+// Questions about every range on one line, generalising the pairwise
+// `contains` and `intersects`. Empty ranges follow the same rules as there:
+// they overlap nothing and are never contained.
+
+// True when one range contains every other range on the line. A line with a
+// single range trivially satisfies this.
+pub fn any_contains_all<T: Ord>(ranges: &[RangeInclusive<T>]) -> bool {
+    let (Some(widest_start), Some(widest_end)) = (
+        ranges.iter().map(|range| range.start()).min(),
+        ranges.iter().map(|range| range.end()).max(),
+    ) else {
+        return false;
+    };
+
+    // Only a range spanning from the smallest start to the largest end can
+    // contain all the others.
+    ranges.iter().enumerate().any(|(i, candidate)| {
+        candidate.start() == widest_start
+            && candidate.end() == widest_end
+            && ranges
+                .iter()
+                .enumerate()
+                .all(|(j, other)| i == j || contains(candidate, other))
+    })
+}
+
+pub fn all_disjoint<T: Ord>(ranges: &[RangeInclusive<T>]) -> bool {
+    overlapping_pair_count(ranges) == 0
+}
+
+// How many pairs of ranges on the line share at least one section. Sorting by
+// start lets each range count the later ranges that begin before it ends, in
+// O(n log n) overall.
+pub fn overlapping_pair_count<T: Ord>(ranges: &[RangeInclusive<T>]) -> usize {
+    let mut sorted: Vec<&RangeInclusive<T>> =
+        ranges.iter().filter(|range| !range.is_empty()).collect();
+    sorted.sort_by(|left, right| left.start().cmp(right.start()));
+
+    sorted
+        .iter()
+        .enumerate()
+        .map(|(i, range)| {
+            let later = &sorted[i + 1..];
+            later.partition_point(|other| other.start() <= range.end())
+        })
+        .sum()
+}
+
+// Counts over many lines of ranges.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GroupSummary {
+    pub lines: usize,
+    // Lines where one range contains all the others.
+    pub containing_all: usize,
+    // Lines where no two ranges overlap.
+    pub all_disjoint: usize,
+    // Overlapping pairs, summed over every line.
+    pub overlapping_pairs: usize,
+}
+
+impl GroupSummary {
+    pub fn record<T: Ord>(&mut self, ranges: &[RangeInclusive<T>]) {
+        let overlapping_pairs = overlapping_pair_count(ranges);
+
+        self.lines += 1;
+        self.overlapping_pairs += overlapping_pairs;
+
+        if any_contains_all(ranges) {
+            self.containing_all += 1;
+        }
+
+        if overlapping_pairs == 0 {
+            self.all_disjoint += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_any_contains_all() {
+        assert!(any_contains_all(&[2..=8, 3..=7, 2..=4, 8..=8]));
+        assert!(any_contains_all(&[5..=5]));
+        assert!(any_contains_all(&[3..=6, 3..=6]));
+        assert!(!any_contains_all(&[2..=7, 3..=8]));
+        assert!(!any_contains_all(&[1..=10, RangeInclusive::new(5, 2)]));
+        assert!(!any_contains_all::<u32>(&[]));
+    }
+
+    #[test]
+    fn test_overlapping_pair_count() {
+        assert_eq!(overlapping_pair_count(&[1..=3, 2..=5, 3..=3, 6..=9]), 3);
+        assert_eq!(overlapping_pair_count(&[1..=10, 2..=2, 4..=4, 6..=6]), 3);
+        assert_eq!(overlapping_pair_count(&[1..=2, 3..=4, 5..=6]), 0);
+
+        assert!(all_disjoint(&[5..=6, 1..=2, 3..=4]));
+        assert!(!all_disjoint(&[1..=2, 2..=3]));
+    }
+}
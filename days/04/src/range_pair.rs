@@ -1,9 +1,17 @@
+use crate::interval_set::Section;
+use std::cmp::Ordering;
 use std::ops::RangeInclusive;
 
+// Empty ranges, such as a reversed `5..=2`, never intersect or contain
+// anything and are never contained.
 pub fn intersects<T>(left: &RangeInclusive<T>, right: &RangeInclusive<T>) -> bool
 where
     T: PartialOrd,
 {
+    if left.is_empty() || right.is_empty() {
+        return false;
+    }
+
     left.contains(right.start())
         || left.contains(right.end())
         || right.contains(left.start())
@@ -14,9 +22,143 @@ pub fn contains<T>(left: &RangeInclusive<T>, right: &RangeInclusive<T>) -> bool
 where
     T: PartialOrd,
 {
+    if left.is_empty() || right.is_empty() {
+        return false;
+    }
+
     left.contains(right.start()) && left.contains(right.end())
 }
 
+// How two assignments relate. The overlapping variants carry the number of
+// sections the two share.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RangeRelation {
+    Disjoint,
+    // No shared sections, but nothing between them either, like 1-3 and 4-6.
+    Adjacent,
+    Overlapping(u64),
+    LeftContainsRight(u64),
+    RightContainsLeft(u64),
+    Equal(u64),
+}
+
+impl RangeRelation {
+    // An empty range is disjoint from everything, even when it sits inside
+    // the other range.
+    pub fn classify<T: Section>(left: &RangeInclusive<T>, right: &RangeInclusive<T>) -> Self {
+        if left.is_empty() || right.is_empty() {
+            return RangeRelation::Disjoint;
+        }
+
+        let (left_start, left_end) = (*left.start(), *left.end());
+        let (right_start, right_end) = (*right.start(), *right.end());
+
+        if left_end < right_start || right_end < left_start {
+            let (first_end, second_start) = if left_end < right_start {
+                (left_end, right_start)
+            } else {
+                (right_end, left_start)
+            };
+
+            return match first_end.next() {
+                Some(after_first) if after_first == second_start => RangeRelation::Adjacent,
+                _ => RangeRelation::Disjoint,
+            };
+        }
+
+        let overlap = T::count(left_start.max(right_start), left_end.min(right_end));
+
+        match (left_start.cmp(&right_start), left_end.cmp(&right_end)) {
+            (Ordering::Equal, Ordering::Equal) => RangeRelation::Equal(overlap),
+            (Ordering::Less | Ordering::Equal, Ordering::Greater | Ordering::Equal) => {
+                RangeRelation::LeftContainsRight(overlap)
+            }
+            (Ordering::Greater | Ordering::Equal, Ordering::Less | Ordering::Equal) => {
+                RangeRelation::RightContainsLeft(overlap)
+            }
+            _ => RangeRelation::Overlapping(overlap),
+        }
+    }
+
+    pub fn overlap_length(&self) -> u64 {
+        match self {
+            RangeRelation::Disjoint | RangeRelation::Adjacent => 0,
+            RangeRelation::Overlapping(length)
+            | RangeRelation::LeftContainsRight(length)
+            | RangeRelation::RightContainsLeft(length)
+            | RangeRelation::Equal(length) => *length,
+        }
+    }
+
+    // True when either range contains the other, equal ranges included.
+    pub fn is_containment(&self) -> bool {
+        matches!(
+            self,
+            RangeRelation::LeftContainsRight(_)
+                | RangeRelation::RightContainsLeft(_)
+                | RangeRelation::Equal(_)
+        )
+    }
+
+    pub fn is_intersection(&self) -> bool {
+        self.overlap_length() > 0
+    }
+}
+
+// How many pairs fall into each `RangeRelation`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RelationSummary {
+    pub disjoint: usize,
+    pub adjacent: usize,
+    pub overlapping: usize,
+    pub left_contains_right: usize,
+    pub right_contains_left: usize,
+    pub equal: usize,
+    // Sections shared, summed over every pair.
+    pub total_overlap: u64,
+}
+
+impl RelationSummary {
+    pub fn record(&mut self, relation: RangeRelation) {
+        match relation {
+            RangeRelation::Disjoint => self.disjoint += 1,
+            RangeRelation::Adjacent => self.adjacent += 1,
+            RangeRelation::Overlapping(_) => self.overlapping += 1,
+            RangeRelation::LeftContainsRight(_) => self.left_contains_right += 1,
+            RangeRelation::RightContainsLeft(_) => self.right_contains_left += 1,
+            RangeRelation::Equal(_) => self.equal += 1,
+        }
+
+        self.total_overlap += relation.overlap_length();
+    }
+
+    pub fn pairs(&self) -> usize {
+        self.disjoint + self.adjacent + self.intersected()
+    }
+
+    // Pairs where one range contains the other, equal ranges included.
+    pub fn contained(&self) -> usize {
+        self.left_contains_right + self.right_contains_left + self.equal
+    }
+
+    // Pairs sharing at least one section.
+    pub fn intersected(&self) -> usize {
+        self.overlapping + self.contained()
+    }
+}
+
+impl FromIterator<RangeRelation> for RelationSummary {
+    fn from_iter<I: IntoIterator<Item = RangeRelation>>(relations: I) -> Self {
+        let mut summary = RelationSummary::default();
+
+        for relation in relations {
+            summary.record(relation);
+        }
+
+        summary
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -31,6 +173,7 @@ mod tests {
 
         assert!(!intersects(&(1..=3), &(4..=6)));
         assert!(!intersects(&(4..=6), &(1..=3)));
+        assert!(!intersects(&(1..=10), &RangeInclusive::new(5, 2)));
     }
 
     #[test]
@@ -42,5 +185,49 @@ mod tests {
         assert!(!contains(&(2..=4), &(1..=3)));
         assert!(!contains(&(1..=3), &(4..=6)));
         assert!(!contains(&(4..=6), &(1..=3)));
+        assert!(!contains(&(1..=10), &RangeInclusive::new(5, 2)));
+    }
+
+    #[test]
+    fn test_classify() {
+        use RangeRelation::*;
+
+        assert_eq!(RangeRelation::classify(&(1..=3), &(5..=6)), Disjoint);
+        assert_eq!(RangeRelation::classify(&(4..=6), &(1..=3)), Adjacent);
+        assert_eq!(RangeRelation::classify(&(1..=4), &(3..=6)), Overlapping(2));
+        assert_eq!(
+            RangeRelation::classify(&(1..=6), &(1..=3)),
+            LeftContainsRight(3)
+        );
+        assert_eq!(
+            RangeRelation::classify(&(2..=2), &(1..=3)),
+            RightContainsLeft(1)
+        );
+        assert_eq!(RangeRelation::classify(&(2..=5), &(2..=5)), Equal(4));
+        assert_eq!(
+            RangeRelation::classify(&(1..=6), &RangeInclusive::new(4, 3)),
+            Disjoint
+        );
+        assert_eq!(
+            RangeRelation::classify(&(0u8..=254), &(255..=255)),
+            Adjacent
+        );
+    }
+
+    #[test]
+    fn test_relation_summary() {
+        let summary: RelationSummary = [
+            RangeRelation::Adjacent,
+            RangeRelation::Overlapping(2),
+            RangeRelation::Equal(3),
+            RangeRelation::RightContainsLeft(1),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(summary.pairs(), 4);
+        assert_eq!(summary.contained(), 2);
+        assert_eq!(summary.intersected(), 3);
+        assert_eq!(summary.total_overlap, 6);
     }
 }